@@ -1,5 +1,5 @@
 use std::str::from_utf8;
-use buf_iter::{BufIter, Result, Span};
+use buf_iter::{html::Html, BufIter, Result, Span};
 
 const BUF: &[u8] = include_bytes!("./index.html");
 
@@ -50,80 +50,3 @@ enum Token {
     /// `any value, white space preserved`
     Text(Span),
 }
-
-#[derive(Debug)]
-enum Html {
-    /// `<!`
-    StartDocTag(Span),
-    /// `</`
-    StartCloseTag(Span),
-    /// `<`
-    StartTag(Span),
-    /// `>`
-    EndTag(Span),
-    /// `=`
-    Eq(Span),
-    /// `"phone"`, quote is excluded
-    Lit(Span),
-    /// `some-value`, tag or attr name
-    Ident(Span),
-}
-
-impl Html {
-    fn parse(input: &mut BufIter) -> Result<Self> {
-        let token = match input.peek_required()? {
-            b'<' => {
-                input.next_peeked();
-                let span = input.span();
-
-                match input.peek_required()? {
-                    b'!' => {
-                        input.next_peeked();
-                        Self::StartDocTag(span.into_spanned(&input.span()))
-                    }
-                    b'/' => {
-                        input.next_peeked();
-                        Self::StartCloseTag(span.into_spanned(&input.span()))
-                    }
-                    _ => Self::StartTag(input.span()),
-                }
-            }
-            b'>' => {
-                input.next_peeked();
-                Self::EndTag(input.span())
-            }
-            b'=' => {
-                input.next_peeked();
-                Self::Eq(input.span())
-            }
-            b'"' => {
-                input.next_peeked();
-                let lit = Self::Lit(input.collect_with(|e|!matches!(e,b'"'))?);
-                input.next_as::<b'"'>()?;
-                lit
-            }
-            _ => {
-                input.skip_whitespaces();
-                Self::Ident(input.collect_with(|e|{
-                    !(e.is_ascii_whitespace() || matches!(e,b'='|b'>'))
-                })?)
-            }
-        };
-
-        Ok(token)
-    }
-
-    fn span(&self) -> &Span {
-        match self {
-            Html::StartDocTag(span) => span,
-            Html::StartCloseTag(span) => span,
-            Html::StartTag(span) => span,
-            Html::EndTag(span) => span,
-            Html::Eq(span) => span,
-            Html::Lit(span) => span,
-            Html::Ident(span) => span,
-        }
-    }
-}
-
-
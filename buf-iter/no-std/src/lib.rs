@@ -0,0 +1,19 @@
+//! build test for `buf-iter` without `std`
+//!
+//! this crate only need to compile, build it on its own so the `std` feature
+//! is not unified from other workspace members
+//!
+//! ```bash
+//! cargo build -p buf-iter-no-std
+//! ```
+#![no_std]
+
+use buf_iter::{BufIter, Result, Span};
+
+/// collect the method of http request line
+pub fn method(buf: &[u8]) -> Result<Span> {
+    let mut iter = BufIter::new(buf);
+    let method = iter.collect_ident()?;
+    iter.next_as::<b' '>()?;
+    Ok(method)
+}
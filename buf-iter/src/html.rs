@@ -0,0 +1,166 @@
+//! html tokens built directly on [`BufIter`]
+//!
+//! tokens are one dimensional, use [`Tag`] to group a tag with its attributes
+use alloc::vec::Vec;
+
+use crate::{BufIter, Error, ErrorKind, Result, Span};
+
+/// a single html token
+#[derive(Debug)]
+pub enum Html {
+    /// `<!`
+    StartDocTag(Span),
+    /// `</`
+    StartCloseTag(Span),
+    /// `<`
+    StartTag(Span),
+    /// `>`
+    EndTag(Span),
+    /// `=`
+    Eq(Span),
+    /// `"phone"`, quote is excluded
+    Lit(Span),
+    /// `some-value`, tag or attr name
+    Ident(Span),
+}
+
+impl Html {
+    /// consume iterator of one token
+    pub fn parse(input: &mut BufIter) -> Result<Self> {
+        let token = match input.peek_required()? {
+            b'<' => {
+                input.next_peeked();
+                let span = input.span();
+
+                match input.peek_required()? {
+                    b'!' => {
+                        input.next_peeked();
+                        Self::StartDocTag(span.into_spanned(&input.span()))
+                    }
+                    b'/' => {
+                        input.next_peeked();
+                        Self::StartCloseTag(span.into_spanned(&input.span()))
+                    }
+                    _ => Self::StartTag(input.span()),
+                }
+            }
+            b'>' => {
+                input.next_peeked();
+                Self::EndTag(input.span())
+            }
+            b'=' => {
+                input.next_peeked();
+                Self::Eq(input.span())
+            }
+            b'"' => {
+                input.next_peeked();
+                let lit = Self::Lit(input.collect_with(|e|!matches!(e,b'"'))?);
+                input.next_as::<b'"'>()?;
+                lit
+            }
+            _ => {
+                input.skip_whitespaces();
+                Self::Ident(input.collect_with(|e|{
+                    !(e.is_ascii_whitespace() || matches!(e,b'='|b'>'))
+                })?)
+            }
+        };
+
+        Ok(token)
+    }
+
+    /// span of current token
+    pub fn span(&self) -> &Span {
+        match self {
+            Html::StartDocTag(span) => span,
+            Html::StartCloseTag(span) => span,
+            Html::StartTag(span) => span,
+            Html::EndTag(span) => span,
+            Html::Eq(span) => span,
+            Html::Lit(span) => span,
+            Html::Ident(span) => span,
+        }
+    }
+
+    /// error pointing at current token
+    fn unexpected(&self, input: &BufIter) -> Error {
+        let span = self.span().clone();
+        let found = input.source()[span.offset];
+        Error::new(span, ErrorKind::Unexpected(found))
+    }
+}
+
+/// open tag with its attributes, `<a href="x" y>`
+#[derive(Debug)]
+pub struct Tag {
+    /// tag name
+    pub name: Span,
+    /// attribute key and optional value, quote is excluded from value
+    pub attrs: Vec<(Span, Option<Span>)>,
+}
+
+impl Tag {
+    /// consume iterator from [`Html::StartTag`] until [`Html::EndTag`]
+    pub fn parse(input: &mut BufIter) -> Result<Self> {
+        match Html::parse(input)? {
+            Html::StartTag(_) => {}
+            html => return Err(html.unexpected(input)),
+        }
+
+        let name = match Html::parse(input)? {
+            Html::Ident(name) => name,
+            html => return Err(html.unexpected(input)),
+        };
+
+        let mut attrs = Vec::new();
+
+        loop {
+            input.skip_whitespaces();
+            let key = match Html::parse(input)? {
+                Html::EndTag(_) => break,
+                Html::Ident(key) => key,
+                html => return Err(html.unexpected(input)),
+            };
+
+            input.skip_whitespaces();
+            if !input.peek_as::<b'='>() {
+                attrs.push((key, None));
+                continue;
+            }
+            input.next_peeked();
+
+            input.skip_whitespaces();
+            match Html::parse(input)? {
+                Html::Lit(value) => attrs.push((key, Some(value))),
+                html => return Err(html.unexpected(input)),
+            }
+        }
+
+        Ok(Self { name, attrs })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tag() {
+        const SRC: &[u8] = br#"<a href="x" y>"#;
+        let mut input = BufIter::new(SRC);
+
+        let tag = Tag::parse(&mut input).unwrap();
+        assert_eq!(tag.name.evaluate(SRC), b"a");
+        assert_eq!(tag.attrs.len(), 2);
+
+        let (key, value) = &tag.attrs[0];
+        assert_eq!(key.evaluate(SRC), b"href");
+        assert_eq!(value.as_ref().map(|e|e.evaluate(SRC)), Some(&b"x"[..]));
+
+        let (key, value) = &tag.attrs[1];
+        assert_eq!(key.evaluate(SRC), b"y");
+        assert!(value.is_none());
+
+        assert!(input.is_empty());
+    }
+}
@@ -1,6 +1,19 @@
 //! buffer iterator
 //!
 //! iterate over buffer using [`BufIter`], peek forward and track position with [`Span`]
+//!
+//! this crate is `no_std`, the `std` feature (enabled by default) only adds
+//! [`std::error::Error`] implementation for [`Error`]
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+pub mod html;
 
 /// buffer iterator
 ///
@@ -48,30 +61,53 @@
 /// let query = iter.collect_with(|e|e!=&b' ').unwrap();
 /// assert_eq!(query.evaluate(SRC), b"?id=4");
 /// ```
+#[derive(Debug, Clone)]
 pub struct BufIter<'r> {
     buf: &'r [u8],
     offset: usize,
     line: usize,
     col: usize,
+    tab_width: usize,
 }
 
 /// Creation
 impl<'r> BufIter<'r> {
     /// create new [`BufIter`]
     pub const fn new(buf: &'r [u8]) -> Self {
-        Self { buf, offset: 0, line: 1, col: 0 }
+        Self { buf, offset: 0, line: 1, col: 0, tab_width: 1 }
     }
 
     /// create new [`BufIter`] starting from given span
     ///
     /// this can be used for partial parsing when reading from io
     pub const fn from_span(buf: &'r [u8], span: Span) -> Self {
-        Self { buf, offset: span.offset, line: span.line, col: span.col }
+        Self { buf, offset: span.offset, line: span.line, col: span.col, tab_width: 1 }
     }
 
     /// clone [`BufIter`] starting from current span
     pub const fn fork(&self) -> Self {
-        Self::from_span(self.buf, Span::new(self.offset, 1, self.line, self.col))
+        Self {
+            tab_width: self.tab_width,
+            ..Self::from_span(self.buf, Span::new(self.offset, 1, self.line, self.col))
+        }
+    }
+
+    /// set `\t` to advance column to the next tab stop of given width
+    ///
+    /// by default, `\t` advance column by one like any other byte
+    pub const fn with_tab_width(mut self, width: usize) -> Self {
+        self.tab_width = width;
+        self
+    }
+
+    /// read all bytes from given reader into [`OwnedBufIter`]
+    ///
+    /// use [`OwnedBufIter::iter`] to start iterating
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: std::io::Read>(mut reader: R) -> std::io::Result<OwnedBufIter> {
+        let mut buf = alloc::vec::Vec::new();
+        reader.read_to_end(&mut buf)?;
+        Ok(OwnedBufIter { buf })
     }
 }
 
@@ -92,9 +128,9 @@ impl<'r> BufIter<'r> {
 
         if val == b'\n' {
             self.line += 1;
-            self.col = 1;
+            self.col = 0;
         } else {
-            self.col += 1;
+            self.col = self.next_col(val);
         }
 
         Ok(val)
@@ -104,10 +140,45 @@ impl<'r> BufIter<'r> {
     ///
     /// convenient function that can check and return detailed error
     pub fn next_as<const B: u8>(&mut self) -> Result<u8> {
+        self.expect_byte(B)
+    }
+
+    /// required next bytes to eq with given byte string, return span of consumed bytes
+    ///
+    /// on mismatch, return [`ErrorKind::ExpectFound`] or [`ErrorKind::ExpectEof`] at the
+    /// mismatching byte, bytes before it and the mismatching byte itself are consumed, same as
+    /// [`BufIter::next_as`], use [`BufIter::fork`] to avoid partial consume
+    pub fn consume_exact(&mut self, expected: &[u8]) -> Result<Span> {
+        let Some((first, rest)) = expected.split_first() else {
+            return Ok(Span::new(self.offset, 0, self.line, self.col + 1));
+        };
+        self.expect_byte(*first)?;
+        let mut span = self.span();
+        for ex in rest {
+            self.expect_byte(*ex)?;
+        }
+        span.len = expected.len();
+        Ok(span)
+    }
+
+    /// [`BufIter::next_as`] with runtime byte
+    fn expect_byte(&mut self, ex: u8) -> Result<u8> {
         match self.next() {
-            Ok(ok) if ok == B => Ok(B),
-            Ok(ok) => Err(self.error(ErrorKind::ExpectFound(B, ok))),
-            Err(err) if err.is_eof() => Err(self.error(ErrorKind::ExpectEof(B))),
+            Ok(ok) if ok == ex => Ok(ex),
+            Ok(ok) => Err(self.error(ErrorKind::ExpectFound(ex, ok))),
+            Err(err) if err.is_eof() => Err(self.error(ErrorKind::ExpectEof(ex))),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// required next byte to be one of given bytes
+    ///
+    /// convenient function that can check and return detailed error
+    pub fn next_one_of(&mut self, set: &[u8]) -> Result<u8> {
+        match self.next() {
+            Ok(ok) if set.contains(&ok) => Ok(ok),
+            Ok(ok) => Err(self.error(ErrorKind::ExpectOneOf(ByteSet::new(set), Some(ok)))),
+            Err(err) if err.is_eof() => Err(self.error(ErrorKind::ExpectOneOf(ByteSet::new(set), None))),
             Err(err) => Err(err),
         }
     }
@@ -150,6 +221,22 @@ impl<'r> BufIter<'r> {
         self.next().expect("peeked");
     }
 
+    /// advance cursor forward only if predicate return true
+    ///
+    /// return [`None`] without advancing if predicate return false or eof, see
+    /// [`std::iter::Peekable::next_if`]
+    pub fn next_if<F>(&mut self, predicate: F) -> Option<u8> where F: Fn(&u8) -> bool {
+        self.peek_if(predicate)?;
+        self.next().ok()
+    }
+
+    /// advance cursor forward only if next byte eq with given byte
+    ///
+    /// return whether the byte is consumed
+    pub fn next_if_eq<const B: u8>(&mut self) -> bool {
+        self.next_if(|b| b == &B).is_some()
+    }
+
     /// keep calling [`BufIter::next`] if whitespace found
     ///
     /// see [`u8::is_ascii_whitespace`] for what considered whitespace
@@ -162,6 +249,118 @@ impl<'r> BufIter<'r> {
         }
     }
 
+    /// push back the last consumed byte, so it will be returned by the next [`BufIter::next`]
+    ///
+    /// line and column are restored, does nothing if no byte has been consumed
+    pub fn rewind_byte(&mut self) {
+        if self.offset == 0 {
+            return;
+        }
+        self.offset -= 1;
+        match self.buf[self.offset] {
+            b'\n' => self.line -= 1,
+            b'\t' if self.tab_width > 1 => {}
+            _ => {
+                self.col -= 1;
+                return;
+            }
+        }
+        // column of newline or tab can only be known from the line start
+        self.col = 0;
+        for b in &self.buf[self.line_start_offset()..self.offset] {
+            self.col = self.next_col(*b);
+        }
+    }
+
+    /// [`BufIter::next`] that also returns span of the consumed byte
+    ///
+    /// the span line and column is the position of the byte itself, same as span returned by
+    /// `collect_*` methods, return [`None`] on EOF
+    pub fn next_spanned(&mut self) -> Option<(Span, u8)> {
+        let span = Span::new(self.offset, 1, self.line, self.col + 1);
+        self.next().ok().map(|byte|(span, byte))
+    }
+
+    /// iterate remaining bytes along with its span, see [`BufIter::next_spanned`]
+    ///
+    /// ```
+    /// # use buf_iter::BufIter;
+    /// let mut iter = BufIter::new(b"a\nb");
+    /// let pos = iter.spanned().map(|(span, _)|span.line_col()).collect::<Vec<_>>();
+    /// assert_eq!(pos, [(1, 1), (1, 2), (2, 1)]);
+    /// ```
+    pub fn spanned(&mut self) -> impl Iterator<Item = (Span, u8)> + use<'_, 'r> {
+        core::iter::from_fn(move ||self.next_spanned())
+    }
+
+    /// advance cursor forward by `n` bytes
+    ///
+    /// return [`ErrorKind::Eof`] if fewer than `n` bytes remain, leaving the cursor at EOF
+    pub fn advance_by(&mut self, n: usize) -> Result<()> {
+        match self.offset.checked_add(n) {
+            Some(end) if end <= self.len() => {
+                self.advance_to(end);
+                Ok(())
+            }
+            _ => {
+                self.advance_to(self.len());
+                Err(self.eof())
+            }
+        }
+    }
+
+    /// lazily split remaining bytes by `SEP`, yielding span of each field
+    ///
+    /// similar to [`str::split`], consecutive or trailing separator yield an empty span, and
+    /// the separators are consumed but not included in the spans
+    ///
+    /// ```
+    /// # use buf_iter::BufIter;
+    /// let src = b"a,,b";
+    /// let mut iter = BufIter::new(src);
+    /// let fields = iter.spans_of::<b','>().map(|e|e.evaluate(src)).collect::<Vec<_>>();
+    /// assert_eq!(fields, [&b"a"[..], b"", b"b"]);
+    /// ```
+    pub fn spans_of<const SEP: u8>(&mut self) -> impl Iterator<Item = Span> + use<'_, 'r, SEP> {
+        let mut done = false;
+        core::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let start = self.offset;
+            let (line, col) = (self.line, self.col + 1);
+            match self.buf[start..].iter().position(|b| *b == SEP) {
+                Some(pos) => {
+                    self.advance_to(start + pos + 1);
+                    Some(Span::new(start, pos, line, col))
+                }
+                None => {
+                    done = true;
+                    self.advance_to(self.len());
+                    Some(Span::new(start, self.offset - start, line, col))
+                }
+            }
+        })
+    }
+
+    /// collecting exactly `n` bytes
+    ///
+    /// return [`ErrorKind::ExpectLen`] without advancing if fewer than `n` bytes remain
+    pub fn collect_n(&mut self, n: usize) -> Result<Span> {
+        if self.remaining() < n {
+            let span = Span::new(self.offset, self.remaining(), self.line, self.col + 1);
+            return Err(Error::new(span, ErrorKind::ExpectLen(n, self.remaining())));
+        }
+        if n == 0 {
+            return Ok(Span::new(self.offset, 0, self.line, self.col + 1));
+        }
+        self.next()?;
+        let mut span = self.span();
+        self.advance_to(span.offset + n);
+        span.len = n;
+        Ok(span)
+    }
+
     /// collecting identifier
     ///
     /// rules:
@@ -171,6 +370,14 @@ impl<'r> BufIter<'r> {
     ///
     /// EOF aware, means will stop instead of return error when EOF after one character
     pub fn collect_ident(&mut self) -> Result<Span> {
+        self.collect_ident_with(u8::is_ascii_alphanumeric)
+    }
+
+    /// [`BufIter::collect_ident`] with given continuation predicate
+    ///
+    /// leading byte rule is kept, alphabetic or `_`, useful for names that contains `-`, `.`
+    /// or `:`
+    pub fn collect_ident_with<F>(&mut self, continue_: F) -> Result<Span> where F: Fn(&u8) -> bool {
         match self.next()? {
             b'_' => {}
             ok if ok.is_ascii_alphabetic() => {}
@@ -179,7 +386,7 @@ impl<'r> BufIter<'r> {
         let span = self.span();
         loop {
             match self.peek() {
-                Some(ok) if ok.is_ascii_alphanumeric() => self.next_peeked(),
+                Some(ok) if continue_(ok) => self.next_peeked(),
                 Some(_) => break,
                 None => break,
             }
@@ -202,16 +409,170 @@ impl<'r> BufIter<'r> {
             Err(err) => return Err(err),
         }
         let span = self.span();
-        loop {
-            match self.peek() {
-                Some(some) if some != &B => self.next_peeked(),
-                Some(_) => break,
-                None => break,
+        let end = match self.buf[self.offset..].iter().position(|b| *b == B) {
+            Some(pos) => self.offset + pos,
+            None => self.len(),
+        };
+        self.advance_to(end);
+        Ok(span.into_spanned(&self.span()))
+    }
+
+    /// collecting until and including specified byte
+    ///
+    /// unlike [`BufIter::collect_as`], the predicate byte is consumed and included in returned
+    /// span, which may only contains the predicate byte
+    ///
+    /// return [`ErrorKind::ExpectEof`] if EOF reached before the predicate byte, leaving the
+    /// cursor at EOF
+    pub fn collect_as_inclusive<const B: u8>(&mut self) -> Result<Span> {
+        let first = match self.next() {
+            Ok(ok) => ok,
+            Err(err) if err.is_eof() => return Err(self.error(ErrorKind::ExpectEof(B))),
+            Err(err) => return Err(err),
+        };
+        let span = self.span();
+        if first != B {
+            match self.buf[self.offset..].iter().position(|b| *b == B) {
+                Some(pos) => self.advance_to(self.offset + pos + 1),
+                None => {
+                    self.advance_to(self.len());
+                    return Err(self.error(ErrorKind::ExpectEof(B)));
+                }
             }
         }
         Ok(span.into_spanned(&self.span()))
     }
 
+    /// collecting until specified byte sequence found
+    ///
+    /// at least one byte must be found before the sequence, otherwise return
+    /// [`ErrorKind::Unexpected`]
+    ///
+    /// the sequence is not included in returned span
+    ///
+    /// EOF aware, means will stop instead of return error, use
+    /// [`BufIter::collect_until_slice_checked`] to know whether the sequence is found
+    pub fn collect_until_slice(&mut self, delim: &[u8]) -> Result<Span> {
+        self.collect_until_slice_checked(delim).map(|(span, _)| span)
+    }
+
+    /// [`BufIter::collect_until_slice`] and return whether the sequence is found
+    ///
+    /// `false` means EOF is reached before the sequence found
+    pub fn collect_until_slice_checked(&mut self, delim: &[u8]) -> Result<(Span, bool)> {
+        if !delim.is_empty() && self.buf[self.offset..].starts_with(delim) {
+            let found = self.next()?;
+            return Err(self.error(ErrorKind::Unexpected(found)));
+        }
+        self.next()?;
+        let span = self.span();
+        let (end, found) = match delim.split_first() {
+            Some((lead, _)) => {
+                let mut end = (self.len(), false);
+                let mut offset = self.offset;
+                while let Some(pos) = self.buf[offset..].iter().position(|b| b == lead) {
+                    if self.buf[offset + pos..].starts_with(delim) {
+                        end = (offset + pos, true);
+                        break;
+                    }
+                    offset += pos + 1;
+                }
+                end
+            }
+            None => (self.offset, true),
+        };
+        self.advance_to(end);
+        Ok((span.into_spanned(&self.span()), found))
+    }
+
+    /// collecting content between balanced delimiters
+    ///
+    /// the next byte must be `OPEN`, nested `OPEN` and `CLOSE` are tracked until the matching
+    /// `CLOSE` found, both delimiters are consumed but not included in returned span
+    ///
+    /// return [`ErrorKind::Unbalanced`] pointing at the opening delimiter if EOF is reached
+    /// before the matching `CLOSE`
+    pub fn collect_balanced<const OPEN: u8, const CLOSE: u8>(&mut self) -> Result<Span> {
+        self.balanced::<OPEN, CLOSE>(false)
+    }
+
+    /// [`BufIter::collect_balanced`] that ignore delimiters inside string literal
+    ///
+    /// string literal is quoted by `"` or `'`, and `\` escape the next byte, so escaped quote
+    /// does not close the literal
+    pub fn collect_balanced_str<const OPEN: u8, const CLOSE: u8>(&mut self) -> Result<Span> {
+        self.balanced::<OPEN, CLOSE>(true)
+    }
+
+    fn balanced<const OPEN: u8, const CLOSE: u8>(&mut self, quoted: bool) -> Result<Span> {
+        self.next_as::<OPEN>()?;
+        let open = self.span();
+        let start = self.offset;
+        let mut inner = None;
+        let mut depth = 1usize;
+        let mut quote = None;
+        loop {
+            let byte = match self.next() {
+                Ok(ok) => ok,
+                Err(err) if err.is_eof() => {
+                    return Err(Error::new(open, ErrorKind::Unbalanced(OPEN, CLOSE)))
+                }
+                Err(err) => return Err(err),
+            };
+            inner.get_or_insert_with(|| self.span());
+            if let Some(q) = quote {
+                if byte == b'\\' {
+                    let _ = self.next();
+                } else if byte == q {
+                    quote = None;
+                }
+            } else if quoted && matches!(byte, b'"' | b'\'') {
+                quote = Some(byte);
+            } else if byte == OPEN {
+                depth += 1;
+            } else if byte == CLOSE {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+        }
+        let mut inner = inner.expect("at least closing delimiter is consumed");
+        inner.len = self.offset - 1 - start;
+        Ok(inner)
+    }
+
+    /// collecting until newline, then consume the newline
+    ///
+    /// both `\n` and `\r\n` are accepted, the newline is not included in returned span,
+    /// an empty line return zero length span
+    ///
+    /// EOF aware, means the last line does not require trailing newline, but return
+    /// [`ErrorKind::Eof`] if there is no remaining bytes
+    pub fn collect_line(&mut self) -> Result<Span> {
+        if self.is_empty() {
+            return Err(self.eof());
+        }
+        let line_end = match self.buf[self.offset..].iter().position(|b| *b == b'\n') {
+            Some(pos) => self.offset + pos,
+            None => self.len(),
+        };
+        let end = match line_end.checked_sub(1) {
+            Some(cr) if cr >= self.offset && self.buf[cr] == b'\r' => cr,
+            _ => line_end,
+        };
+        let span = if end == self.offset {
+            Span::new(self.offset, 0, self.line, self.col + 1)
+        } else {
+            self.next_peeked();
+            let span = self.span();
+            self.advance_to(end);
+            span.into_spanned(&self.span())
+        };
+        self.advance_to((line_end + 1).min(self.len()));
+        Ok(span)
+    }
+
     /// collecting with predicate
     ///
     /// keep collecting while predicate return true
@@ -220,22 +581,151 @@ impl<'r> BufIter<'r> {
     ///
     /// EOF aware, means will stop instead of return error
     pub fn collect_with<F>(&mut self, predicate: F) -> Result<Span> where F: Fn(&u8) -> bool {
+        self.collect_with_pos(|b, _| predicate(b))
+    }
+
+    /// [`BufIter::collect_with`] that also returns the count of bytes collected
+    ///
+    /// useful to validate exact length field without measuring the span again
+    pub fn collect_while_count<F>(&mut self, predicate: F) -> Result<(Span, usize)> where F: Fn(&u8) -> bool {
+        let span = self.collect_with(predicate)?;
+        let count = span.len;
+        Ok((span, count))
+    }
+
+    /// [`BufIter::collect_with`] with the count of bytes collected so far passed to predicate
+    ///
+    /// the first byte is called with `0`
+    pub fn collect_with_pos<F>(&mut self, predicate: F) -> Result<Span> where F: Fn(&u8, usize) -> bool {
         match self.next()? {
-            b if predicate(&b) => {}
+            b if predicate(&b, 0) => {}
             b => return Err(self.error(ErrorKind::Unexpected(b))),
         }
         let span = self.span();
+        let mut pos = 1;
         loop {
             match self.peek() {
-                Some(some) if predicate(some) => self.next_peeked(),
+                Some(some) if predicate(some, pos) => self.next_peeked(),
                 Some(_) => break,
                 None => break,
             }
+            pos += 1;
+        }
+        Ok(span.into_spanned(&self.span()))
+    }
+
+    /// collecting utf8 characters with predicate
+    ///
+    /// keep collecting while predicate return true, the returned span is in bytes
+    ///
+    /// at least one predicate must success, otherwise return [`ErrorKind::Unexpected`],
+    /// invalid utf8 return [`ErrorKind::Utf8`]
+    ///
+    /// EOF aware, means will stop instead of return error
+    pub fn collect_while_char<F>(&mut self, predicate: F) -> Result<Span> where F: Fn(char) -> bool {
+        if self.is_empty() {
+            return Err(self.eof());
+        }
+        let start = self.offset;
+        let mut end = self.offset;
+        loop {
+            match self.decode_char(end) {
+                Ok(Some((ch, len))) if predicate(ch) => end += len,
+                Ok(_) => break,
+                Err(err) => {
+                    self.advance_to(end);
+                    self.next_peeked();
+                    return Err(self.error(ErrorKind::Utf8(err)));
+                }
+            }
+        }
+        self.next_peeked();
+        if end == start {
+            return Err(self.error(ErrorKind::Unexpected(self.buf[start])));
         }
+        let span = self.span();
+        self.advance_to(end);
         Ok(span.into_spanned(&self.span()))
     }
 }
 
+/// Combinator
+impl<'r> BufIter<'r> {
+    /// try each alternative in order and return the first success
+    ///
+    /// iterator is rewound between failed alternatives, so each alternative start from the
+    /// same position, if all alternatives fail, the last error is returned with the iterator
+    /// rewound
+    ///
+    /// # Panic
+    ///
+    /// panic if `alts` is empty
+    #[allow(clippy::type_complexity)]
+    pub fn first_of<T>(&mut self, alts: &[&dyn Fn(&mut BufIter<'r>) -> Result<T>]) -> Result<T> {
+        let (offset, line, col) = (self.offset, self.line, self.col);
+        let mut last = None;
+        for alt in alts {
+            match alt(self) {
+                Ok(ok) => return Ok(ok),
+                Err(err) => last = Some(err),
+            }
+            (self.offset, self.line, self.col) = (offset, line, col);
+        }
+        Err(last.expect("at least one alternative"))
+    }
+}
+
+/// Fast path
+impl<'r> BufIter<'r> {
+    /// decode utf8 character at given offset without advancing iterator
+    ///
+    /// return the character and its length in bytes, or [`None`] if eof
+    fn decode_char(&self, offset: usize) -> core::result::Result<Option<(char, usize)>, core::str::Utf8Error> {
+        let rest = &self.buf[offset..];
+        let len = match rest.first() {
+            Some(0xc0..=0xdf) => 2,
+            Some(0xe0..=0xef) => 3,
+            Some(0xf0..=0xf7) => 4,
+            Some(_) => 1,
+            None => return Ok(None),
+        };
+        let ch = core::str::from_utf8(&rest[..len.min(rest.len())])?;
+        Ok(ch.chars().next().map(|ch|(ch, len)))
+    }
+
+    /// advance cursor to given offset without visiting each byte
+    ///
+    /// line and col is fixed up by counting newlines only in the skipped region
+    fn advance_to(&mut self, end: usize) {
+        let skipped = &self.buf[self.offset..end];
+        let tail = match skipped.iter().rposition(|b| *b == b'\n') {
+            Some(last) => {
+                self.line += skipped.iter().filter(|b| **b == b'\n').count();
+                self.col = 0;
+                &skipped[last + 1..]
+            }
+            None => skipped,
+        };
+        if self.tab_width > 1 {
+            for b in tail {
+                self.col = self.next_col(*b);
+            }
+        } else {
+            self.col += tail.len();
+        }
+        self.offset = end;
+    }
+
+    /// column after consuming given byte, other than `\n`
+    const fn next_col(&self, byte: u8) -> usize {
+        if byte == b'\t' && self.tab_width > 1 {
+            (self.col / self.tab_width + 1) * self.tab_width
+        } else {
+            self.col + 1
+        }
+    }
+}
+
 /// Peek forward without advancing iterator
 impl<'r> BufIter<'r> {
     /// peek the next byte without advancing iterator
@@ -245,15 +735,24 @@ impl<'r> BufIter<'r> {
         self.buf.get(self.offset)
     }
 
-    /// peek n byte forward without advancing parser
+    /// peek n byte forward without advancing iterator
     ///
-    /// n is 0 indexed, so `buf_iter.peek_n(0)` will peek 1 forward
+    /// n is 0 indexed, so `buf_iter.peek_n(0)` is the same as [`BufIter::peek`], and
+    /// `buf_iter.peek_n(1)` is the byte after it
     ///
-    /// return [`None`] if eof, to return error instead use [`Self::peek_required`]
+    /// return [`None`] if eof, to return error instead use [`Self::peek_n_required`]
     pub fn peek_n(&self, n: usize) -> Option<&u8> {
         self.buf.get(self.offset + n)
     }
 
+    /// [`BufIter::peek_n`] and return [`Error`] if eof
+    pub fn peek_n_required(&self, n: usize) -> Result<&u8> {
+        match self.peek_n(n) {
+            Some(some) => Ok(some),
+            None => Err(self.eof()),
+        }
+    }
+
     /// [`BufIter::peek`] and return [`Error`] if eof
     pub fn peek_required(&self) -> Result<&u8> {
         match self.peek() {
@@ -269,6 +768,11 @@ impl<'r> BufIter<'r> {
         matches!(self.peek(), Some(b) if b == &B)
     }
 
+    /// [`BufIter::peek`] and return the byte only if predicate return true
+    pub fn peek_if<F>(&self, predicate: F) -> Option<&u8> where F: Fn(&u8) -> bool {
+        self.peek().filter(|b| predicate(b))
+    }
+
     /// [`BufIter::peek_required`] and [`BufIter::peek_as`]
     pub fn peek_required_as<const B: u8>(&self) -> Result<bool> {
         Ok(self.peek_required()? == &B)
@@ -277,11 +781,46 @@ impl<'r> BufIter<'r> {
 
 /// Utility
 impl<'r> BufIter<'r> {
+    /// rewind iterator to the beginning of source buffer, same state as [`BufIter::new`]
+    pub fn reset(&mut self) {
+        (self.offset, self.line, self.col) = (0, 1, 0);
+    }
+
     /// return source buffer
-    pub const fn source(&self) -> &[u8] {
+    pub const fn source(&self) -> &'r [u8] {
         self.buf
     }
 
+    /// bytes that have not been consumed
+    pub const fn remaining_slice(&self) -> &'r [u8] {
+        self.buf.split_at(self.offset).1
+    }
+
+    /// offset where current line begins, the offset after the last consumed `\n`, or 0
+    pub fn line_start_offset(&self) -> usize {
+        match self.consumed_slice().iter().rposition(|b| *b == b'\n') {
+            Some(pos) => pos + 1,
+            None => 0,
+        }
+    }
+
+    /// bytes of current line, from [`BufIter::line_start_offset`] until the next `\n`
+    ///
+    /// the newline is not included, consumed and remaining bytes of the line are both included
+    pub fn current_line(&self) -> &'r [u8] {
+        let start = self.line_start_offset();
+        let end = match self.buf[self.offset..].iter().position(|b| *b == b'\n') {
+            Some(pos) => self.offset + pos,
+            None => self.len(),
+        };
+        &self.buf[start..end]
+    }
+
+    /// bytes that have been consumed
+    pub const fn consumed_slice(&self) -> &'r [u8] {
+        self.buf.split_at(self.offset).0
+    }
+
     /// source buffer len
     pub const fn len(&self) -> usize {
         self.buf.len()
@@ -312,6 +851,20 @@ impl<'r> BufIter<'r> {
         Span::new(self.offset - 1, 1, self.line, self.col)
     }
 
+    /// create [`Span`] from given checkpoint to current position
+    ///
+    /// checkpoint is a span previously returned by [`BufIter::span`], the checkpoint byte is
+    /// included, same as [`Span::into_spanned`] with current span
+    pub const fn span_from(&self, checkpoint: Span) -> Span {
+        let len = self.offset.saturating_sub(checkpoint.offset);
+        Span::new(checkpoint.offset, len, checkpoint.line, checkpoint.col)
+    }
+
+    /// bytes from given checkpoint to current position, see [`BufIter::span_from`]
+    pub fn slice_from(&self, checkpoint: Span) -> &'r [u8] {
+        &self.buf[self.span_from(checkpoint).range()]
+    }
+
     /// create [`Error`] at current span
     pub const fn error(&self, kind: ErrorKind) -> Error {
         Error::new(self.span(), kind)
@@ -321,6 +874,14 @@ impl<'r> BufIter<'r> {
     pub const fn eof(&self) -> Error {
         Error::eof(self.span())
     }
+
+    /// create [`Error`] at current span with [`ErrorKind::Custom`] message
+    ///
+    /// for domain specific error without wrapping [`Error`] in another type
+    #[cfg(feature = "alloc")]
+    pub fn custom_error(&self, msg: impl Into<alloc::borrow::Cow<'static, str>>) -> Error {
+        self.error(ErrorKind::Custom(msg.into()))
+    }
 }
 
 impl<'r> From<&'r [u8]> for BufIter<'r> {
@@ -335,12 +896,115 @@ impl<'r> From<(&'r [u8],Span)> for BufIter<'r> {
     }
 }
 
+/// buffer iterator that owns its source buffer
+///
+/// created by [`BufIter::from_reader`]
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct OwnedBufIter {
+    buf: alloc::vec::Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl OwnedBufIter {
+    /// create [`BufIter`] over owned source buffer
+    pub const fn iter(&self) -> BufIter<'_> {
+        BufIter::new(self.buf.as_slice())
+    }
+
+    /// return source buffer
+    pub const fn source(&self) -> &[u8] {
+        self.buf.as_slice()
+    }
+
+    /// return owned source buffer
+    pub fn into_inner(self) -> alloc::vec::Vec<u8> {
+        self.buf
+    }
+}
+
+/// buffer iterator that read from [`std::io::Read`] as needed
+///
+/// read bytes are kept, so spans created stay valid against [`ReadBufIter::source`]
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ReadBufIter<R> {
+    reader: R,
+    buf: alloc::vec::Vec<u8>,
+    offset: usize,
+    line: usize,
+    col: usize,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> ReadBufIter<R> {
+    /// create new [`ReadBufIter`]
+    pub const fn new(reader: R) -> Self {
+        Self { reader, buf: alloc::vec::Vec::new(), offset: 0, line: 1, col: 0 }
+    }
+
+    /// advance cursor forward by a byte, read from reader if needed
+    ///
+    /// possible error is [`ErrorKind::Eof`] or [`ErrorKind::Io`]
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<u8> {
+        self.fill(1)?;
+
+        let val = self.buf[self.offset];
+
+        self.offset += 1;
+
+        if val == b'\n' {
+            self.line += 1;
+            self.col = 0;
+        } else {
+            self.col += 1;
+        }
+
+        Ok(val)
+    }
+
+    /// peek n bytes window forward without advancing iterator
+    ///
+    /// read from reader until n bytes is buffered, return [`ErrorKind::Eof`] if reader
+    /// ends first
+    pub fn peek_n_slice(&mut self, n: usize) -> Result<&[u8]> {
+        self.fill(n)?;
+        Ok(&self.buf[self.offset..self.offset + n])
+    }
+
+    /// return buffered source
+    pub fn source(&self) -> &[u8] {
+        self.buf.as_slice()
+    }
+
+    /// read until at least n bytes after cursor is buffered
+    fn fill(&mut self, n: usize) -> Result<()> {
+        let mut chunk = [0u8; 1024];
+        while self.buf.len() - self.offset < n {
+            match self.reader.read(&mut chunk) {
+                Ok(0) => return Err(self.error(ErrorKind::Eof)),
+                Ok(read) => self.buf.extend_from_slice(&chunk[..read]),
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(self.error(ErrorKind::Io(err.kind()))),
+            }
+        }
+        Ok(())
+    }
+
+    /// create [`Error`] at current position
+    fn error(&self, kind: ErrorKind) -> Error {
+        Error::new(Span::new(self.offset, 0, self.line, self.col), kind)
+    }
+}
+
 /// a 'pointer' of a value from source buffer
 ///
 /// the struct only contain 4 usize, which is cheap to clone
 ///
 /// use [`Span::evaluate`] to get actual value from given buffer
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Span {
     pub offset: usize,
     pub len: usize,
@@ -359,14 +1023,47 @@ impl Span {
         Self { offset: 0, len: 0, line: 0, col: 0 }
     }
 
+    /// returns (line, column) of the source
+    pub const fn line_col(&self) -> (usize,usize) {
+        (self.line,self.col)
+    }
+
     /// is current span unknown, see [`Self::unknown`]
     pub const fn is_unknown(&self) -> bool {
         self.offset == 0 && self.len == 0 && self.line == 0 && self.col == 0
     }
 
+    /// is current span zero length
+    ///
+    /// unlike [`Self::is_unknown`], empty span may point at a real offset, and evaluate to an
+    /// empty slice
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// return byte range of current span
+    pub const fn range(&self) -> core::ops::Range<usize> {
+        self.offset..self.offset + self.len
+    }
+
     /// return actual value from given source buffer
+    ///
+    /// # Panic
+    ///
+    /// panic if span is out of range of given buffer, use [`Span::try_evaluate`] to return
+    /// [`None`] instead
     pub fn evaluate<'r>(&self, buf: &'r [u8]) -> &'r [u8] {
-        &buf[self.offset..self.offset + self.len]
+        match self.try_evaluate(buf) {
+            Some(value) => value,
+            None => panic!("span {:?} out of range for buffer of len {}", self.range(), buf.len()),
+        }
+    }
+
+    /// return actual value from given source buffer, or [`None`] if span is out of range
+    ///
+    /// spans created against different buffer may be out of range
+    pub fn try_evaluate<'r>(&self, buf: &'r [u8]) -> Option<&'r [u8]> {
+        buf.get(self.range())
     }
 
     /// set length from current span to given span
@@ -379,10 +1076,43 @@ impl Span {
         self.len = span.offset - self.offset + 1;
         self
     }
+
+    /// [`Display`][core::fmt::Display] start and end position, computed from given buffer
+    ///
+    /// formatted as `line:col-line:col`, where the end is the position of the last byte
+    pub fn display_with<'r>(&self, buf: &'r [u8]) -> DisplayWith<'r> {
+        DisplayWith { span: self.clone(), buf }
+    }
 }
 
-/// parsing error [`std::result::Result`] alias
-pub type Result<T,E = Error> = std::result::Result<T,E>;
+/// formatted as `offset..offset+len (line:col)`
+impl core::fmt::Display for Span {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}..{} ({}:{})", self.offset, self.offset + self.len, self.line, self.col)
+    }
+}
+
+/// span display with end position, created by [`Span::display_with`]
+#[derive(Debug)]
+pub struct DisplayWith<'r> {
+    span: Span,
+    buf: &'r [u8],
+}
+
+impl core::fmt::Display for DisplayWith<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Span { offset, len, line, col } = self.span;
+        let head = self.buf.get(offset..offset + len.saturating_sub(1)).unwrap_or_default();
+        let (end_line, end_col) = head.iter().fold((line, col), |(line, col), b| match b {
+            b'\n' => (line + 1, 1),
+            _ => (line, col + 1),
+        });
+        write!(f, "{line}:{col}-{end_line}:{end_col}")
+    }
+}
+
+/// parsing error [`core::result::Result`] alias
+pub type Result<T,E = Error> = core::result::Result<T,E>;
 
 /// parsing error
 #[derive(Debug, PartialEq,Eq)]
@@ -392,7 +1122,10 @@ pub struct Error {
 }
 
 /// parsing error kind
+///
+/// some variants only exist with `alloc` or `std` feature, so matching requires a wildcard arm
 #[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ErrorKind {
     /// unexpected eof
     Eof,
@@ -402,10 +1135,24 @@ pub enum ErrorKind {
     ExpectEof(u8),
     /// expect `_`, found `_`
     ExpectFound(u8,u8),
+    /// expect one of `_`, found `_` or EOF
+    ExpectOneOf(ByteSet, Option<u8>),
+    /// expect `_` bytes, found only `_` bytes before EOF
+    ExpectLen(usize, usize),
     /// expect alphabetical found `_`
     ExpectAlphabetic(u8),
     /// expect identifier found `_`
     ExpectIdent(u8),
+    /// delimiter `_` is not closed by `_` before EOF
+    Unbalanced(u8,u8),
+    /// invalid utf8
+    Utf8(core::str::Utf8Error),
+    /// user defined error message, see [`BufIter::custom_error`]
+    #[cfg(feature = "alloc")]
+    Custom(alloc::borrow::Cow<'static, str>),
+    /// io error when reading source
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
 }
 
 impl Error {
@@ -425,17 +1172,18 @@ impl Error {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for Error { }
 
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(&self.kind, f)
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.kind, f)
     }
 }
 
-impl std::fmt::Display for ErrorKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use std::fmt::Write;
+impl core::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use core::fmt::Write;
         match self {
             Self::Eof => f.write_str("unexpected EOF"),
             Self::Unexpected(fd) => {
@@ -455,6 +1203,28 @@ impl std::fmt::Display for ErrorKind {
                 f.write_char(*fd as char)?;
                 f.write_char('`')
             }
+            ErrorKind::ExpectOneOf(ex, fd) => {
+                f.write_str("expect one of ")?;
+                for (i, ex) in ex.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_char('`')?;
+                    f.write_char(ex as char)?;
+                    f.write_char('`')?;
+                }
+                match fd {
+                    Some(fd) => {
+                        f.write_str(" found `")?;
+                        f.write_char(*fd as char)?;
+                        f.write_char('`')
+                    }
+                    None => f.write_str(" found EOF"),
+                }
+            }
+            ErrorKind::ExpectLen(ex, fd) => {
+                write!(f, "expect {ex} bytes found {fd} bytes before EOF")
+            }
             ErrorKind::ExpectAlphabetic(fd) => {
                 f.write_str("expect alphabetical found `")?;
                 f.write_char(*fd as char)?;
@@ -465,7 +1235,55 @@ impl std::fmt::Display for ErrorKind {
                 f.write_char(*fd as char)?;
                 f.write_char('`')
             }
+            ErrorKind::Unbalanced(open, close) => {
+                f.write_str("unbalanced `")?;
+                f.write_char(*open as char)?;
+                f.write_str("`, expect `")?;
+                f.write_char(*close as char)?;
+                f.write_str("` found EOF")
+            }
+            ErrorKind::Utf8(err) => write!(f, "invalid utf8: {err}"),
+            #[cfg(feature = "alloc")]
+            ErrorKind::Custom(msg) => f.write_str(msg),
+            #[cfg(feature = "std")]
+            ErrorKind::Io(kind) => write!(f, "io error: {kind}"),
         }
     }
 }
 
+/// set of bytes, used by [`ErrorKind::ExpectOneOf`]
+///
+/// the set is stored as bitmap, so it can be copied into error without borrowing the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ByteSet([u64; 4]);
+
+impl ByteSet {
+    /// create new [`ByteSet`] from given bytes
+    pub const fn new(bytes: &[u8]) -> Self {
+        let mut set = [0u64; 4];
+        let mut i = 0;
+        while i < bytes.len() {
+            let b = bytes[i] as usize;
+            set[b / 64] |= 1 << (b % 64);
+            i += 1;
+        }
+        Self(set)
+    }
+
+    /// check is given byte in the set
+    pub const fn contains(&self, byte: u8) -> bool {
+        let b = byte as usize;
+        self.0[b / 64] & (1 << (b % 64)) != 0
+    }
+
+    /// iterate bytes in the set in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=u8::MAX).filter(|b| self.contains(*b))
+    }
+}
+
+impl From<&[u8]> for ByteSet {
+    fn from(value: &[u8]) -> Self {
+        Self::new(value)
+    }
+}
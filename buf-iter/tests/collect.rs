@@ -0,0 +1,372 @@
+use buf_iter::{BufIter, ErrorKind};
+
+#[test]
+fn collect_as_line_col() {
+    const SRC: &[u8] = b"ab\ncd\n\nefg<tail";
+
+    let mut fast = BufIter::new(SRC);
+    let mut slow = BufIter::new(SRC);
+
+    let fast_span = fast.collect_as::<b'<'>().unwrap();
+    let slow_span = slow.collect_with(|e|e != &b'<').unwrap();
+
+    assert_eq!(fast_span, slow_span);
+    assert_eq!(fast.span(), slow.span());
+    assert_eq!(fast_span.evaluate(SRC), b"ab\ncd\n\nefg");
+
+    assert_eq!(fast.next(), Ok(b'<'));
+    assert_eq!(slow.next(), Ok(b'<'));
+    assert_eq!(fast.span(), slow.span());
+}
+
+#[test]
+fn collect_as_eof() {
+    const SRC: &[u8] = b"no\ndelimiter";
+
+    let mut fast = BufIter::new(SRC);
+    let mut slow = BufIter::new(SRC);
+
+    let fast_span = fast.collect_as::<b'<'>().unwrap();
+    let slow_span = slow.collect_with(|e|e != &b'<').unwrap();
+
+    assert_eq!(fast_span, slow_span);
+    assert_eq!(fast.span(), slow.span());
+    assert!(fast.is_empty());
+}
+
+#[test]
+fn collect_until_slice() {
+    const SRC: &[u8] = b"<!-- a - b -\n-> -->rest";
+
+    let mut iter = BufIter::new(SRC);
+    iter.collect_until_slice(b"-").unwrap();
+
+    let mut slow = BufIter::new(SRC);
+    slow.collect_with(|e|e != &b'-').unwrap();
+    assert_eq!(iter.span(), slow.span());
+
+    let comment = iter.collect_until_slice(b"-->").unwrap();
+    assert_eq!(comment.evaluate(SRC), b"-- a - b -\n-> ");
+    assert_eq!(iter.peek(), Some(&b'-'));
+
+    let mut slow = BufIter::new(SRC);
+    slow.collect_with(|e|e != &b'-').unwrap();
+    for _ in 0..comment.len {
+        slow.next().unwrap();
+    }
+    assert_eq!(iter.span(), slow.span());
+}
+
+#[test]
+fn collect_as_large_buffer() {
+    let mut src = b"<html>".to_vec();
+    while src.len() < 1024 * 1024 {
+        src.extend_from_slice(b"lorem ipsum dolor sit amet\n");
+    }
+    src.push(b'<');
+
+    let mut fast = BufIter::new(&src);
+    fast.next_as::<b'<'>().unwrap();
+    let fast_span = fast.collect_as::<b'<'>();
+
+    let mut slow = BufIter::new(&src);
+    slow.next_as::<b'<'>().unwrap();
+    let slow_span = slow.collect_with(|e|e != &b'<');
+
+    assert_eq!(fast_span, slow_span);
+    assert_eq!(fast.span(), slow.span());
+    assert_eq!(fast.remaining(), 1);
+}
+
+#[test]
+fn collect_line() {
+    const SRC: &[u8] = b"a\r\nb";
+    let mut iter = BufIter::new(SRC);
+    assert_eq!(iter.collect_line().unwrap().evaluate(SRC), b"a");
+    assert_eq!(iter.collect_line().unwrap().evaluate(SRC), b"b");
+    assert!(iter.collect_line().unwrap_err().is_eof());
+
+    const LF: &[u8] = b"a\nb";
+    let mut iter = BufIter::new(LF);
+    assert_eq!(iter.collect_line().unwrap().evaluate(LF), b"a");
+    assert_eq!(iter.next(), Ok(b'b'));
+    assert_eq!(iter.span().line_col(), (2, 1));
+
+    const EMPTY: &[u8] = b"key: value\r\n\r\nbody";
+    let mut iter = BufIter::new(EMPTY);
+    assert_eq!(iter.collect_line().unwrap().evaluate(EMPTY), b"key: value");
+    assert_eq!(iter.collect_line().unwrap().len, 0);
+    assert_eq!(iter.collect_line().unwrap().evaluate(EMPTY), b"body");
+    assert!(iter.is_empty());
+}
+
+#[test]
+fn collect_while_char() {
+    const SRC: &[u8] = "héllo wörld".as_bytes();
+    let mut iter = BufIter::new(SRC);
+
+    let word = iter.collect_while_char(char::is_alphabetic).unwrap();
+    assert_eq!(word.evaluate(SRC), "héllo".as_bytes());
+    assert_eq!(iter.next(), Ok(b' '));
+
+    let word = iter.collect_while_char(char::is_alphabetic).unwrap();
+    assert_eq!(word.evaluate(SRC), "wörld".as_bytes());
+    assert!(iter.is_empty());
+
+    const INVALID: &[u8] = b"ab\xffcd";
+    let mut iter = BufIter::new(INVALID);
+    let err = iter.collect_while_char(char::is_alphabetic).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::Utf8(_)));
+    assert_eq!(err.span.offset, 2);
+}
+
+#[test]
+fn consumed_remaining_slice() {
+    const SRC: &[u8] = b"key: value";
+    let mut iter = BufIter::new(SRC);
+    assert_eq!(iter.consumed_slice(), b"");
+    assert_eq!(iter.remaining_slice(), SRC);
+
+    iter.collect_as::<b':'>().unwrap();
+    assert_eq!(iter.consumed_slice(), b"key");
+    assert_eq!(iter.remaining_slice(), b": value");
+}
+
+#[test]
+fn collect_until_slice_checked() {
+    const SRC: &[u8] = b"<!-- closed -->";
+    let mut iter = BufIter::new(SRC);
+    iter.collect_until_slice(b" ").unwrap();
+    let (comment, found) = iter.collect_until_slice_checked(b"-->").unwrap();
+    assert_eq!(comment.evaluate(SRC), b" closed ");
+    assert!(found);
+    assert_eq!(iter.remaining_slice(), b"-->");
+
+    const TRUNCATED: &[u8] = b"<!-- open --";
+    let mut iter = BufIter::new(TRUNCATED);
+    iter.collect_until_slice(b" ").unwrap();
+    let (comment, found) = iter.collect_until_slice_checked(b"-->").unwrap();
+    assert_eq!(comment.evaluate(TRUNCATED), b" open --");
+    assert!(!found);
+    assert!(iter.is_empty());
+}
+
+#[test]
+fn collect_with_pos() {
+    fn ident(b: &u8, pos: usize) -> bool {
+        b.is_ascii_alphabetic() || b == &b'_' || (pos != 0 && b.is_ascii_digit())
+    }
+
+    const SRC: &[u8] = b"a1b2 c";
+    let mut iter = BufIter::new(SRC);
+    let span = iter.collect_with_pos(ident).unwrap();
+    assert_eq!(span.evaluate(SRC), b"a1b2");
+    assert_eq!(iter.peek(), Some(&b' '));
+
+    let mut iter = BufIter::new(b"1abc");
+    let err = iter.collect_with_pos(ident).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Unexpected(b'1'));
+}
+
+#[test]
+fn reset() {
+    const SRC: &[u8] = b"ab\ncd";
+    let mut iter = BufIter::new(SRC);
+    iter.collect_line().unwrap();
+    iter.next().unwrap();
+
+    iter.reset();
+    assert_eq!(iter.remaining(), SRC.len());
+    assert_eq!(iter.next(), Ok(b'a'));
+
+    let mut fresh = BufIter::new(SRC);
+    fresh.next().unwrap();
+    assert_eq!(iter.span(), fresh.span());
+}
+
+#[test]
+fn advance_by() {
+    const SRC: &[u8] = b"ab\ncd\nef";
+    let mut iter = BufIter::new(SRC);
+    iter.advance_by(4).unwrap();
+    let span = iter.span();
+    assert_eq!(iter.consumed_slice(), b"ab\nc");
+    assert_eq!((span.line, span.col), (2, 1));
+    assert_eq!(iter.next(), Ok(b'd'));
+
+    let err = iter.advance_by(10).unwrap_err();
+    assert!(err.is_eof());
+    assert_eq!(iter.remaining(), 0);
+    let span = iter.span();
+    assert_eq!((span.line, span.col), (3, 2));
+}
+
+#[test]
+fn collect_balanced() {
+    const SRC: &[u8] = b"{a{b}c} d";
+    let mut iter = BufIter::new(SRC);
+    let span = iter.collect_balanced::<b'{', b'}'>().unwrap();
+    assert_eq!(span.evaluate(SRC), b"a{b}c");
+    assert_eq!(span.col, 2);
+    assert_eq!(iter.peek(), Some(&b' '));
+
+    let mut iter = BufIter::new(b"{}");
+    let span = iter.collect_balanced::<b'{', b'}'>().unwrap();
+    assert_eq!(span.len, 0);
+
+    let mut iter = BufIter::new(b"{a{b}");
+    let err = iter.collect_balanced::<b'{', b'}'>().unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Unbalanced(b'{', b'}'));
+    assert_eq!(err.span.offset, 0);
+    assert_eq!(err.to_string(), "unbalanced `{`, expect `}` found EOF");
+}
+
+#[test]
+fn collect_balanced_str() {
+    const SRC: &[u8] = br#"{ "}" }"#;
+    let mut iter = BufIter::new(SRC);
+    let span = iter.collect_balanced_str::<b'{', b'}'>().unwrap();
+    assert_eq!(span.evaluate(SRC), br#" "}" "#);
+
+    const ESCAPED: &[u8] = br#"{ "a\"}" '{' }"#;
+    let mut iter = BufIter::new(ESCAPED);
+    let span = iter.collect_balanced_str::<b'{', b'}'>().unwrap();
+    assert_eq!(span.evaluate(ESCAPED), br#" "a\"}" '{' "#);
+    assert!(iter.is_empty());
+
+    let mut iter = BufIter::new(br#"{ "}" "#);
+    let err = iter.collect_balanced_str::<b'{', b'}'>().unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Unbalanced(b'{', b'}'));
+}
+
+#[test]
+fn slice_from() {
+    const SRC: &[u8] = b"  foo_1 bar";
+    let mut iter = BufIter::new(SRC);
+    iter.skip_whitespaces();
+    iter.next().unwrap();
+    let checkpoint = iter.span();
+    iter.collect_with(|b| b.is_ascii_alphanumeric() || b == &b'_').unwrap();
+
+    assert_eq!(iter.slice_from(checkpoint.clone()), b"foo_1");
+    let span = iter.span_from(checkpoint);
+    assert_eq!(span.evaluate(SRC), b"foo_1");
+    assert_eq!((span.line, span.col), (1, 3));
+}
+
+#[test]
+fn collect_n() {
+    const SRC: &[u8] = b"# abcd\nef";
+    let mut iter = BufIter::new(SRC);
+    iter.next_as::<b'#'>().unwrap();
+    iter.skip_whitespaces();
+    let span = iter.collect_n(4).unwrap();
+    assert_eq!(span.evaluate(SRC), b"abcd");
+    assert_eq!((span.line, span.col), (1, 3));
+
+    let span = iter.collect_n(2).unwrap();
+    assert_eq!(span.evaluate(SRC), b"\ne");
+    assert_eq!(iter.span().line, 2);
+
+    let err = iter.collect_n(2).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ExpectLen(2, 1));
+    assert_eq!(err.to_string(), "expect 2 bytes found 1 bytes before EOF");
+    assert_eq!(iter.next(), Ok(b'f'));
+}
+
+#[test]
+fn spans_of() {
+    const SRC: &[u8] = b"a,,b\n,cd";
+    let mut iter = BufIter::new(SRC);
+    let spans = iter.spans_of::<b','>().collect::<Vec<_>>();
+    let fields = spans.iter().map(|e| e.evaluate(SRC)).collect::<Vec<_>>();
+    assert_eq!(fields, [&b"a"[..], b"", b"b\n", b"cd"]);
+    assert_eq!((spans[1].offset, spans[1].len), (2, 0));
+    assert_eq!((spans[3].line, spans[3].col), (2, 2));
+    assert!(iter.is_empty());
+
+    let mut iter = BufIter::new(b"a,b");
+    let mut spans = iter.spans_of::<b','>();
+    spans.next().unwrap();
+    drop(spans);
+    assert_eq!(iter.remaining_slice(), b"b");
+}
+
+#[test]
+fn collect_ident_with() {
+    const SRC: &[u8] = b"data-role=x";
+    let mut iter = BufIter::new(SRC);
+    let span = iter.collect_ident_with(|b| b.is_ascii_alphanumeric() || b == &b'-').unwrap();
+    assert_eq!(span.evaluate(SRC), b"data-role");
+    assert_eq!(iter.peek(), Some(&b'='));
+
+    const NS: &[u8] = b"ns:tag>";
+    let mut iter = BufIter::new(NS);
+    let span = iter.collect_ident_with(|b| b.is_ascii_alphanumeric() || b == &b':').unwrap();
+    assert_eq!(span.evaluate(NS), b"ns:tag");
+
+    let mut iter = BufIter::new(NS);
+    assert_eq!(iter.collect_ident().unwrap().evaluate(NS), b"ns");
+
+    let mut iter = BufIter::new(b"-x");
+    assert!(iter.collect_ident_with(|b| b == &b'-').is_err());
+}
+
+#[test]
+fn rewind_byte() {
+    const SRC: &[u8] = b"ab\ncd";
+
+    let mut iter = BufIter::new(SRC);
+    iter.rewind_byte();
+    assert_eq!(iter.peek(), Some(&b'a'));
+
+    assert_eq!(iter.next(), Ok(b'a'));
+    iter.rewind_byte();
+    assert_eq!(iter.next(), Ok(b'a'));
+
+    // each rewind should land at the same state as consuming one byte less
+    iter.advance_by(3).unwrap();
+    for consumed in (1..4).rev() {
+        iter.rewind_byte();
+        let mut expected = BufIter::new(SRC);
+        expected.advance_by(consumed).unwrap();
+        assert_eq!(iter.span(), expected.span());
+        assert_eq!(iter.peek(), expected.peek());
+    }
+    assert_eq!(iter.next(), Ok(b'b'));
+}
+
+#[test]
+fn collect_while_count() {
+    const SRC: &[u8] = b"#c0ffee;";
+
+    let mut iter = BufIter::new(SRC);
+    iter.next_as::<b'#'>().unwrap();
+    let (span, count) = iter.collect_while_count(u8::is_ascii_hexdigit).unwrap();
+    assert_eq!(count, 6);
+    assert_eq!(count, span.len);
+    assert_eq!(span.evaluate(SRC), b"c0ffee");
+
+    let err = iter.collect_while_count(u8::is_ascii_hexdigit).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Unexpected(b';'));
+}
+
+#[test]
+fn collect_as_inclusive() {
+    const SRC: &[u8] = b"ab\n\ncd";
+
+    let mut iter = BufIter::new(SRC);
+    let line = iter.collect_as_inclusive::<b'\n'>().unwrap();
+    assert_eq!(line.evaluate(SRC), b"ab\n");
+    assert_eq!(line.evaluate(SRC).last(), Some(&b'\n'));
+
+    let line = iter.collect_as_inclusive::<b'\n'>().unwrap();
+    assert_eq!(line.evaluate(SRC), b"\n");
+
+    let mut fork = iter.fork();
+    assert_eq!(fork.collect_as::<b'\n'>().unwrap().evaluate(SRC), b"cd");
+
+    let err = iter.collect_as_inclusive::<b'\n'>().unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ExpectEof(b'\n'));
+    assert!(iter.is_empty());
+}
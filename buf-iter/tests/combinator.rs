@@ -0,0 +1,110 @@
+use buf_iter::{BufIter, ByteSet, ErrorKind, Span};
+
+#[test]
+fn first_of() {
+    const SRC: &[u8] = b"key: value";
+
+    let quoted = |iter: &mut BufIter| -> buf_iter::Result<Span> {
+        iter.next_as::<b'"'>()?;
+        iter.collect_as::<b'"'>()
+    };
+    let ident = |iter: &mut BufIter| iter.collect_ident();
+
+    let mut iter = BufIter::new(SRC);
+    let key = iter.first_of(&[&quoted, &ident]).unwrap();
+    assert_eq!(key.evaluate(SRC), b"key");
+    assert_eq!(iter.next(), Ok(b':'));
+
+    let mut iter = BufIter::new(SRC);
+    let key = iter.first_of(&[&ident, &quoted]).unwrap();
+    assert_eq!(key.evaluate(SRC), b"key");
+
+    let mut iter = BufIter::new(SRC);
+    let err = iter.first_of(&[&quoted]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ExpectFound(b'"', b'k'));
+    assert_eq!(iter.remaining(), SRC.len());
+}
+
+#[test]
+fn next_if() {
+    fn int(iter: &mut BufIter) -> buf_iter::Result<(bool, Span)> {
+        let neg = iter.next_if_eq::<b'-'>();
+        let digits = iter.collect_with(u8::is_ascii_digit)?;
+        Ok((neg, digits))
+    }
+
+    const SRC: &[u8] = b"-42 7";
+    let mut iter = BufIter::new(SRC);
+
+    let (neg, digits) = int(&mut iter).unwrap();
+    assert!(neg);
+    assert_eq!(digits.evaluate(SRC), b"42");
+
+    assert_eq!(iter.peek_if(u8::is_ascii_digit), None);
+    assert_eq!(iter.peek_if(u8::is_ascii_whitespace), Some(&b' '));
+    assert_eq!(iter.next_if(u8::is_ascii_digit), None);
+    assert_eq!(iter.next_if(u8::is_ascii_whitespace), Some(b' '));
+
+    let remaining = iter.remaining();
+    assert!(!iter.next_if_eq::<b'-'>());
+    assert_eq!(iter.remaining(), remaining);
+
+    let (neg, digits) = int(&mut iter).unwrap();
+    assert!(!neg);
+    assert_eq!(digits.evaluate(SRC), b"7");
+    assert_eq!(iter.next_if(|_|true), None);
+}
+
+#[test]
+fn next_one_of() {
+    const QUOTES: &[u8] = b"'\"";
+
+    let mut iter = BufIter::new(b"'a'x");
+    assert_eq!(iter.next_one_of(QUOTES), Ok(b'\''));
+    iter.next().unwrap();
+    assert_eq!(iter.next_one_of(QUOTES), Ok(b'\''));
+
+    let err = iter.next_one_of(QUOTES).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ExpectOneOf(ByteSet::new(QUOTES), Some(b'x')));
+    assert_eq!(err.to_string(), "expect one of `\"`, `'` found `x`");
+
+    let err = iter.next_one_of(QUOTES).unwrap_err();
+    assert_eq!(err.to_string(), "expect one of `\"`, `'` found EOF");
+
+    let set = [b'a', b'b'];
+    let mut iter = BufIter::new(b"c");
+    let err = iter.next_one_of(&set).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ExpectOneOf(ByteSet::new(b"ba"), Some(b'c')));
+}
+
+#[test]
+fn peek_n_required() {
+    let mut iter = BufIter::new(b"abc");
+    iter.next().unwrap();
+    assert_eq!(iter.peek_n_required(0), Ok(&b'b'));
+    assert_eq!(iter.peek_n_required(1), Ok(&b'c'));
+    assert_eq!(iter.peek_n(0), iter.peek());
+
+    let err = iter.peek_n_required(2).unwrap_err();
+    assert!(err.is_eof());
+    assert_eq!(iter.next(), Ok(b'b'));
+}
+
+#[test]
+fn consume_exact() {
+    const SRC: &[u8] = b"<!-- x";
+    let mut iter = BufIter::new(SRC);
+    let span = iter.consume_exact(b"<!--").unwrap();
+    assert_eq!(span.evaluate(SRC), b"<!--");
+    assert_eq!(iter.peek(), Some(&b' '));
+
+    let mut iter = BufIter::new(b"<!-x");
+    let err = iter.consume_exact(b"<!--").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ExpectFound(b'-', b'x'));
+    assert_eq!(err.span.offset, 3);
+    assert!(iter.is_empty());
+
+    let mut iter = BufIter::new(b"<!");
+    let err = iter.consume_exact(b"<!--").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ExpectEof(b'-'));
+}
@@ -1,4 +1,4 @@
-use buf_iter::BufIter;
+use buf_iter::{BufIter, ErrorKind};
 
 const SRC: &[u8] = b"\
 GET /health?id=4 HTTP/1.1\r
@@ -65,3 +65,25 @@ fn http_parse() -> Result<(),Box<dyn std::error::Error>> {
 }
 
 
+
+#[test]
+fn http_custom_error() {
+    const SRC: &[u8] = b"GET / HTTP/9.9\r\n";
+
+    let mut iter = BufIter::new(SRC);
+    iter.collect_as::<b' '>().unwrap();
+    iter.next_as::<b' '>().unwrap();
+    iter.collect_as::<b' '>().unwrap();
+    iter.next_as::<b' '>().unwrap();
+
+    let version = iter.collect_as::<b'\r'>().unwrap();
+    assert_ne!(version.evaluate(SRC), b"HTTP/1.1");
+
+    let err = iter.custom_error("invalid HTTP version");
+    assert_eq!(err.kind, ErrorKind::Custom("invalid HTTP version".into()));
+    assert_eq!(err.to_string(), "invalid HTTP version");
+    assert_eq!(err.span.offset, version.offset + version.len - 1);
+
+    let err = iter.custom_error(format!("unsupported {}", "9.9"));
+    assert_eq!(err.kind.to_string(), "unsupported 9.9");
+}
@@ -0,0 +1,59 @@
+use buf_iter::{BufIter, ReadBufIter};
+use std::io::Cursor;
+
+#[test]
+fn from_reader() -> Result<(),Box<dyn std::error::Error>> {
+    let reader = Cursor::new(b"GET /health HTTP/1.1\r\n".to_vec());
+    let owned = BufIter::from_reader(reader)?;
+    let src = owned.source();
+    let mut iter = owned.iter();
+
+    let method = iter.collect_ident()?;
+    assert_eq!(method.evaluate(src), b"GET");
+    iter.next_as::<b' '>()?;
+
+    let path = iter.collect_as::<b' '>()?;
+    assert_eq!(path.evaluate(src), b"/health");
+    iter.next_as::<b' '>()?;
+
+    let version = iter.collect_as::<b'\r'>()?;
+    assert_eq!(version.evaluate(src), b"HTTP/1.1");
+
+    Ok(())
+}
+
+/// reader that deliver 2 bytes at a time
+struct Chunked<'r>(&'r [u8]);
+
+impl std::io::Read for Chunked<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = self.0.len().min(buf.len()).min(2);
+        buf[..len].copy_from_slice(&self.0[..len]);
+        self.0 = &self.0[len..];
+        Ok(len)
+    }
+}
+
+#[test]
+fn peek_n_slice() {
+    let mut iter = ReadBufIter::new(Chunked(b"GET / HTTP/1.1"));
+
+    assert_eq!(iter.peek_n_slice(4), Ok(&b"GET "[..]));
+    assert_eq!(iter.next(), Ok(b'G'));
+    assert_eq!(iter.peek_n_slice(4), Ok(&b"ET /"[..]));
+    assert_eq!(iter.source(), b"GET / ");
+
+    assert!(iter.peek_n_slice(14).unwrap_err().is_eof());
+    assert_eq!(iter.peek_n_slice(13), Ok(&b"ET / HTTP/1.1"[..]));
+}
+
+#[test]
+fn read_line_col() {
+    let mut iter = ReadBufIter::new(Chunked(b"a\nb"));
+    for _ in 0..3 {
+        iter.next().unwrap();
+    }
+    let err = iter.next().unwrap_err();
+    assert!(err.is_eof());
+    assert_eq!(err.span.line_col(), (2, 1));
+}
@@ -0,0 +1,124 @@
+use buf_iter::{BufIter, Span};
+
+#[test]
+fn line_col() {
+    let mut iter = BufIter::new(b"ab\ncd");
+    for _ in 0..4 {
+        iter.next().unwrap();
+    }
+    assert_eq!(iter.span().line_col(), (2, 1));
+}
+
+#[test]
+fn line_col_collect_as() {
+    let mut iter = BufIter::new(b"ab\ncd");
+    iter.collect_as::<b'c'>().unwrap();
+    assert_eq!(iter.span().line_col(), (2, 0));
+    iter.next().unwrap();
+    assert_eq!(iter.span().line_col(), (2, 1));
+}
+
+#[test]
+fn next_spanned() {
+    let mut iter = BufIter::new(b"a\nb");
+    assert_eq!(iter.next_spanned(), Some((Span::new(0, 1, 1, 1), b'a')));
+    assert_eq!(iter.next_spanned(), Some((Span::new(1, 1, 1, 2), b'\n')));
+    assert_eq!(iter.next_spanned(), Some((Span::new(2, 1, 2, 1), b'b')));
+    assert_eq!(iter.next_spanned(), None);
+}
+
+#[test]
+fn display() {
+    let span = Span::new(1, 3, 1, 1);
+    assert_eq!(span.to_string(), "1..4 (1:1)");
+
+    const SRC: &[u8] = b"ab\ncd<";
+    let span = BufIter::new(SRC).collect_as::<b'<'>().unwrap();
+    assert_eq!(span.display_with(SRC).to_string(), "1:1-2:2");
+
+    let mut iter = BufIter::new(SRC);
+    iter.collect_as::<b'\n'>().unwrap();
+    iter.next().unwrap();
+    let span = iter.collect_as::<b'<'>().unwrap();
+    assert_eq!(span.display_with(SRC).to_string(), "2:1-2:2");
+
+    assert_eq!(Span::new(0, 0, 1, 1).display_with(b"").to_string(), "1:1-1:1");
+}
+
+#[test]
+fn try_evaluate() {
+    let span = Span::new(2, 3, 1, 3);
+    assert_eq!(span.range(), 2..5);
+    assert_eq!(span.try_evaluate(b"abcdef"), Some(&b"cde"[..]));
+    assert_eq!(span.try_evaluate(b"abcd"), None);
+    assert_eq!(Span::new(4, 0, 1, 5).try_evaluate(b"abcd"), Some(&b""[..]));
+}
+
+#[test]
+fn tab_width() {
+    const SRC: &[u8] = b"a\tb\n\t\tc\n  \td";
+
+    let mut iter = BufIter::new(SRC).with_tab_width(4);
+    let mut cols = vec![];
+    while !iter.is_empty() {
+        if iter.next().unwrap().is_ascii_alphabetic() {
+            cols.push(iter.span().line_col());
+        }
+    }
+    assert_eq!(cols, [(1, 1), (1, 5), (2, 9), (3, 5)]);
+
+    let mut fast = BufIter::new(SRC).with_tab_width(4);
+    fast.collect_as::<b'd'>().unwrap();
+    fast.next().unwrap();
+    assert_eq!(fast.span().line_col(), (3, 5));
+
+    let mut iter = BufIter::new(SRC);
+    iter.collect_as::<b'b'>().unwrap();
+    iter.next().unwrap();
+    assert_eq!(iter.span().line_col(), (1, 3));
+}
+
+#[test]
+fn is_empty() {
+    let span = Span::new(5, 0, 1, 6);
+    assert!(span.is_empty());
+    assert!(!span.is_unknown());
+    assert_eq!(span.evaluate(b"abcde"), b"");
+    assert_eq!(span.evaluate(b"abcdefg"), b"");
+
+    assert!(Span::unknown().is_empty());
+    assert!(!Span::new(5, 1, 1, 6).is_empty());
+}
+
+#[test]
+fn current_line() {
+    const SRC: &[u8] = b"ab\ncde\n\nf";
+    let mut iter = BufIter::new(SRC);
+    assert_eq!(iter.line_start_offset(), 0);
+    assert_eq!(iter.current_line(), b"ab");
+
+    iter.advance_by(3).unwrap();
+    assert_eq!(iter.line_start_offset(), 3);
+    assert_eq!(iter.current_line(), b"cde");
+
+    iter.advance_by(2).unwrap();
+    assert_eq!(iter.line_start_offset(), 3);
+    assert_eq!(iter.current_line(), b"cde");
+
+    iter.advance_by(2).unwrap();
+    assert_eq!(iter.line_start_offset(), 7);
+    assert_eq!(iter.current_line(), b"");
+
+    iter.advance_by(2).unwrap();
+    assert_eq!(iter.line_start_offset(), 8);
+    assert_eq!(iter.current_line(), b"f");
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde() {
+    let span = Span::new(3, 2, 2, 1);
+    let json = serde_json::to_string(&span).unwrap();
+    assert_eq!(json, r#"{"offset":3,"len":2,"line":2,"col":1}"#);
+    assert_eq!(serde_json::from_str::<Span>(&json).unwrap(), span);
+}
@@ -1,6 +1,9 @@
-use ::tokenizer::{span::{Span, Spanned}, tokenizer::{Peekable as Peekable1, Tokenizer as Tokenizer1}, TokenTree as Tree1};
+use ::tokenizer::{span::{Span, Spanned}, tokenizer::{Peekable as Peekable1, Tokenizer as Tokenizer1}, DebugWith, TokenTree as Tree1};
 use error::{Error, Result};
 
+#[cfg(feature = "full-entities")]
+mod entity_table;
+
 macro_rules! next {
     ($iter:ident) => {
         match $iter.next() {
@@ -23,6 +26,87 @@ macro_rules! peeked {
     () => { concat!("[",line!(),":",column!(),"] should be peeked before") };
 }
 
+/// find region delimited by `<!-- BEGIN name -->` and `<!-- END name -->` marker comments
+///
+/// returns span of everything between the markers, or [`None`] if the markers are not found
+/// or the source failed to parse
+///
+/// ```
+/// # use html_parser::extract_region;
+/// # use tokenizer::span::Spanned;
+/// let src = b"<p><!-- BEGIN a --><b>x</b><!-- END a --></p>";
+/// let region = extract_region(src, "a").unwrap();
+/// assert_eq!(region.evaluate(src), b"<b>x</b>");
+/// ```
+pub fn extract_region(src: &[u8], name: &str) -> Option<Span> {
+    let is_marker = |tree: &SyntaxTree, marker: &str| match tree {
+        SyntaxTree::Comment(comment) => {
+            let content = comment.content(src).trim_ascii();
+            content.strip_prefix(marker.as_bytes())
+                .and_then(|e|e.strip_prefix(b" "))
+                .is_some_and(|e|e.trim_ascii() == name.as_bytes())
+        }
+        _ => false,
+    };
+
+    let mut trees = tokenizer::Tokenizer::new(src);
+    let begin = loop {
+        let tree = trees.next()?.ok()?;
+        if is_marker(&tree, "BEGIN") {
+            break tree.span();
+        }
+    };
+
+    let start = begin.offset() + begin.len();
+    let mut line_col = None;
+    loop {
+        let tree = trees.next()?.ok()?;
+        let span = tree.span();
+        let (line, col) = *line_col.get_or_insert(span.line_col());
+        if is_marker(&tree, "END") {
+            return Some(Span::new(start, span.offset() - start, line, col));
+        }
+    }
+}
+
+/// document totals, see [`stats`]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DocStats {
+    /// open elements, close elements are not counted
+    pub elements: usize,
+    pub text_nodes: usize,
+    pub comments: usize,
+    pub doctypes: usize,
+    /// deepest element nesting, void elements like `<br>` does not add depth
+    pub max_depth: usize,
+}
+
+/// count document totals in one pass without building tree
+pub fn stats(src: &[u8]) -> Result<DocStats> {
+    let mut stats = DocStats::default();
+    let mut depth = 0usize;
+
+    for tree in tokenizer::Tokenizer::new(src) {
+        match tree? {
+            SyntaxTree::Comment(_) => stats.comments += 1,
+            SyntaxTree::DOCTYPE(_) => stats.doctypes += 1,
+            SyntaxTree::Text(_) => stats.text_nodes += 1,
+            SyntaxTree::Element(element) => match element.kind {
+                ElementKind::Open => {
+                    stats.elements += 1;
+                    if !element.tag(src).is_void() {
+                        depth += 1;
+                        stats.max_depth = stats.max_depth.max(depth);
+                    }
+                }
+                ElementKind::Close => depth = depth.saturating_sub(1),
+            },
+        }
+    }
+
+    Ok(stats)
+}
+
 /// tokens that can appear in html
 #[derive(Debug)]
 pub enum SyntaxTree {
@@ -34,6 +118,37 @@ pub enum SyntaxTree {
     Text(Text),
 }
 
+impl SyntaxTree {
+    /// [`Debug`][std::fmt::Debug] output showing evaluated text and position
+    ///
+    /// formatted as `Element("<a>" @ 1:1)`, see [`DebugWith`]
+    pub fn debug<'r>(&self, buf: &'r [u8]) -> DebugWith<'r> {
+        let name = match self {
+            SyntaxTree::Comment(_) => "Comment",
+            SyntaxTree::DOCTYPE(_) => "DOCTYPE",
+            SyntaxTree::Element(_) => "Element",
+            SyntaxTree::Text(_) => "Text",
+        };
+        DebugWith::new(name, self.span(), buf)
+    }
+
+    /// attributes of an element, [`None`] for other tree
+    pub fn attrs(&self) -> Option<&[Attr]> {
+        match self {
+            SyntaxTree::Element(element) => Some(element.attrs()),
+            _ => None,
+        }
+    }
+
+    /// tag name of an element, [`None`] for other tree
+    pub fn tag_name<'r>(&self, buf: &'r [u8]) -> Option<&'r str> {
+        match self {
+            SyntaxTree::Element(element) => std::str::from_utf8(element.tag.evaluate(buf)).ok(),
+            _ => None,
+        }
+    }
+}
+
 /// `<!-- comment -->`
 #[derive(Debug)]
 pub struct Comment {
@@ -42,16 +157,16 @@ pub struct Comment {
 
 impl Comment {
     fn peek(iter: &mut Peekable1<4>, buf: &[u8]) -> bool {
-        if !matches!(iter.peek_n(0),Some(Tree1::Punct(punct)) if punct.evaluate(buf)[0] == b'<') {
+        if !matches!(iter.peek_n(0),Some(Tree1::Punct(punct)) if punct.as_byte(buf) == b'<') {
             return false;
         }
-        if !matches!(iter.peek_n(1),Some(Tree1::Punct(punct)) if punct.evaluate(buf)[0] == b'!') {
+        if !matches!(iter.peek_n(1),Some(Tree1::Punct(punct)) if punct.as_byte(buf) == b'!') {
             return false;
         }
-        if !matches!(iter.peek_n(2),Some(Tree1::Punct(punct)) if punct.evaluate(buf)[0] == b'-') {
+        if !matches!(iter.peek_n(2),Some(Tree1::Punct(punct)) if punct.as_byte(buf) == b'-') {
             return false;
         }
-        if !matches!(iter.peek_n(3),Some(Tree1::Punct(punct)) if punct.evaluate(buf)[0] == b'-') {
+        if !matches!(iter.peek_n(3),Some(Tree1::Punct(punct)) if punct.as_byte(buf) == b'-') {
             return false;
         }
         true
@@ -65,23 +180,17 @@ impl Comment {
 
         let mut span = tree.span();
 
-        'outer: loop {
-            match next!(iter) {
-                Tree1::Punct(punct) if punct.evaluate(buf)[0] == b'-' => {}
-                _ => { continue }
-            }
-
-            match next!(iter) {
-                Tree1::Punct(punct) if punct.evaluate(buf)[0] == b'-' => {}
-                _ => { continue }
-            }
-
-            loop {
-                match next!(iter) {
-                    Tree1::Punct(punct) if punct.evaluate(buf)[0] == b'>' => { break 'outer }
-                    Tree1::Punct(punct) if punct.evaluate(buf)[0] == b'-' => { continue }
-                    _ => { continue 'outer; }
-                }
+        // comment ends at `>` preceded by at least two `-`, so `--` alone is content
+        let mut dashes = 0;
+        loop {
+            let Some(tree) = iter.next() else {
+                let open = span.with_len(4);
+                return Err(Error::new(open, "unterminated comment"));
+            };
+            match tree {
+                Tree1::Punct(punct) if punct.as_byte(buf) == b'-' => dashes += 1,
+                Tree1::Punct(punct) if punct.as_byte(buf) == b'>' && dashes >= 2 => break,
+                _ => dashes = 0,
             }
         }
 
@@ -89,22 +198,32 @@ impl Comment {
 
         Ok(Self { span })
     }
+
+    /// text between `<!--` and `-->`, inner whitespace is kept
+    ///
+    /// empty comment `<!---->` returns an empty slice
+    pub fn content<'r>(&self, buf: &'r [u8]) -> &'r [u8] {
+        let comment = self.evaluate(buf);
+        comment.get(4..comment.len().saturating_sub(3)).unwrap_or_default()
+    }
 }
 
 /// `<!DOCTYPE html>`
 #[derive(Debug)]
 pub struct DOCTYPE {
-    span: Span
+    span: Span,
+    name: Option<Span>,
+    dtd: Option<Span>,
 }
 
 impl DOCTYPE {
     fn peek(iter: &mut Peekable1<4>, buf: &[u8]) -> bool {
         match iter.peek_n(0) {
-            Some(Tree1::Punct(punct)) if punct.evaluate(buf)[0] == b'<' => {},
+            Some(Tree1::Punct(punct)) if punct.as_byte(buf) == b'<' => {},
             _ => return false
         }
         match iter.peek_n(1) {
-            Some(Tree1::Punct(punct)) if punct.evaluate(buf)[0] == b'!' => {},
+            Some(Tree1::Punct(punct)) if punct.as_byte(buf) == b'!' => {},
             _ => return false
         }
         true
@@ -116,16 +235,51 @@ impl DOCTYPE {
 
         let mut span = tree.span();
 
+        // `DOCTYPE` keyword, document type name, then the rest is dtd
+        let mut keyword = false;
+        let mut name = None;
+        let mut dtd: Option<(Span, Span)> = None;
+
         loop {
             match next!(iter) {
-                Tree1::Punct(punct) if punct.evaluate(buf)[0] == b'>' => break,
-                _ => {}
+                Tree1::Punct(punct) if punct.as_byte(buf) == b'>' => break,
+                Tree1::Whitespace(_) => {}
+                _ if !keyword => keyword = true,
+                Tree1::Ident(ident) if name.is_none() && dtd.is_none() => name = Some(ident.span()),
+                tree => match &mut dtd {
+                    Some((_, end)) => *end = tree.span(),
+                    None => dtd = Some((tree.span(), tree.span())),
+                },
             }
         }
 
         span.spanned_into(iter.span());
 
-        Ok(Self { span })
+        let dtd = dtd.map(|(start, end)|{
+            let (line, col) = start.line_col();
+            Span::new(start.offset(), end.offset() + end.len() - start.offset(), line, col)
+        });
+
+        Ok(Self { span, name, dtd })
+    }
+
+    /// document type name, the token after `DOCTYPE`, e.g. `html`
+    pub fn name<'r>(&self, buf: &'r [u8]) -> Option<&'r str> {
+        std::str::from_utf8(self.name.as_ref()?.evaluate(buf)).ok()
+    }
+
+    /// the public or system identifier after the name, trailing whitespace excluded
+    ///
+    /// e.g. `PUBLIC "-//W3C//DTD HTML 4.01//EN"`, html5 doctype have none
+    pub fn dtd(&self) -> Option<Span> {
+        self.dtd.clone()
+    }
+
+    /// is html5 doctype, `<!DOCTYPE html>`, case insensitive
+    ///
+    /// other doctype is legacy, which may trigger quirks mode in browsers
+    pub fn is_html5(&self, buf: &[u8]) -> bool {
+        self.dtd.is_none() && self.name(buf).is_some_and(|name|name.eq_ignore_ascii_case("html"))
     }
 }
 
@@ -133,6 +287,7 @@ impl DOCTYPE {
 pub struct Element {
     pub tag: Ident,
     pub kind: ElementKind,
+    attrs: Vec<Attr>,
     span: Span,
 }
 
@@ -147,6 +302,97 @@ impl From<::tokenizer::Ident> for Ident {
     }
 }
 
+impl Ident {
+    /// continue identifier with adjacent `-` and identifier, e.g. `custom-el`
+    fn parse_name(ident: ::tokenizer::Ident, iter: &mut Peekable1<4>, buf: &[u8]) -> Self {
+        let mut span = ident.span();
+        loop {
+            let next = match iter.peek_n(0) {
+                Some(Tree1::Ident(ident)) => ident.span(),
+                Some(Tree1::Punct(punct)) if punct.as_byte(buf) == b'-' => punct.span(),
+                _ => break,
+            };
+            if next.offset() != span.offset() + span.len() {
+                break;
+            }
+            iter.next().expect(peeked!());
+            span.spanned_into(next);
+        }
+        Self { span }
+    }
+}
+
+macro_rules! tag_names {
+    ($($variant:ident => $name:literal,)*) => {
+        /// classified element tag name, see [`Element::tag`]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum TagName {
+            $(
+                #[doc = concat!("`<", $name, ">`")]
+                $variant,
+            )*
+            /// unknown or custom element, with span of the tag name
+            Other(Span),
+        }
+
+        impl TagName {
+            /// classify tag name case insensitively
+            pub fn classify(span: Span, buf: &[u8]) -> Self {
+                let tag = span.evaluate(buf);
+                $(
+                    if tag.eq_ignore_ascii_case($name.as_bytes()) {
+                        return Self::$variant;
+                    }
+                )*
+                Self::Other(span)
+            }
+
+            /// lowercase tag name, [`None`] for [`TagName::Other`]
+            pub const fn as_str(&self) -> Option<&'static str> {
+                match self {
+                    $(Self::$variant => Some($name),)*
+                    Self::Other(_) => None,
+                }
+            }
+        }
+    };
+}
+
+tag_names! {
+    A => "a", Abbr => "abbr", Area => "area", Article => "article", Aside => "aside",
+    Audio => "audio", B => "b", Base => "base", Blockquote => "blockquote", Body => "body",
+    Br => "br", Button => "button", Canvas => "canvas", Code => "code", Col => "col",
+    Details => "details", Div => "div", Em => "em", Embed => "embed", Footer => "footer",
+    Form => "form", H1 => "h1", H2 => "h2", H3 => "h3", H4 => "h4", H5 => "h5", H6 => "h6",
+    Head => "head", Header => "header", Hr => "hr", Html => "html", I => "i",
+    Iframe => "iframe", Img => "img", Input => "input", Label => "label", Li => "li",
+    Link => "link", Main => "main", Meta => "meta", Nav => "nav", Noscript => "noscript",
+    Ol => "ol", Option => "option", P => "p", Pre => "pre", Script => "script",
+    Section => "section", Select => "select", Source => "source", Span => "span",
+    Strong => "strong", Style => "style", Summary => "summary", Svg => "svg",
+    Table => "table", Tbody => "tbody", Td => "td", Template => "template",
+    Textarea => "textarea", Th => "th", Thead => "thead", Title => "title", Tr => "tr",
+    Track => "track", Ul => "ul", Video => "video", Wbr => "wbr",
+}
+
+impl TagName {
+    /// void element which have no content and no close tag, like `<br>`
+    pub const fn is_void(&self) -> bool {
+        matches!(
+            self,
+            Self::Area | Self::Base | Self::Br | Self::Col | Self::Embed | Self::Hr | Self::Img |
+            Self::Input | Self::Link | Self::Meta | Self::Source | Self::Track | Self::Wbr
+        )
+    }
+
+    /// element which content is text until its close tag, like `<script>`
+    ///
+    /// includes escapable raw text element, `<textarea>` and `<title>`
+    pub const fn is_raw_text(&self) -> bool {
+        matches!(self, Self::Script | Self::Style | Self::Textarea | Self::Title)
+    }
+}
+
 #[derive(Debug)]
 pub enum ElementKind {
     Open,
@@ -155,7 +401,7 @@ pub enum ElementKind {
 
 impl Element {
     fn peek(iter: &mut Peekable1<4>, buf: &[u8]) -> bool {
-        matches!(iter.peek_n(0),Some(Tree1::Punct(punct)) if punct.evaluate(buf)[0] == b'<')
+        matches!(iter.peek_n(0),Some(Tree1::Punct(punct)) if punct.as_byte(buf) == b'<')
     }
 
     fn parse(iter: &mut Peekable1<4>, buf: &[u8]) -> Result<Self> {
@@ -163,7 +409,7 @@ impl Element {
         let (tag,kind) = 'out: {
             match next!(iter) {
                 Tree1::Ident(tag) => break 'out (tag,ElementKind::Open),
-                Tree1::Punct(punct) if punct.evaluate(buf)[0] == b'/' => {}
+                Tree1::Punct(punct) if punct.as_byte(buf) == b'/' => {}
                 _ => return Err(Error::new(iter.span(), "expected `/` or an identifier"))
             }
             match next!(iter) {
@@ -171,30 +417,32 @@ impl Element {
                 _ => return Err(Error::new(iter.span(), "expected an identifier"))
             }
         };
+        let tag = Ident::parse_name(tag, iter, buf);
 
         let mut span = lt.span();
 
         if let ElementKind::Close = kind {
             loop {
                 match next!(iter) {
-                    Tree1::Punct(punct) if punct.evaluate(buf)[0] == b'>' => break,
+                    Tree1::Punct(punct) if punct.as_byte(buf) == b'>' => break,
                     Tree1::Whitespace(_) => continue,
                     _ => return Err(Error::new(iter.span(), "expected `>`"))
                 }
             }
             span.spanned_into(iter.span());
-            return Ok(Self { kind, span, tag: tag.into() });
+            return Ok(Self { kind, span, tag, attrs: vec![] });
         }
 
         // attributes
+        let mut attrs = vec![];
         loop {
             match peek!(iter) {
-                Tree1::Punct(punct) if punct.evaluate(buf)[0] == b'>' => break,
+                Tree1::Punct(punct) if punct.as_byte(buf) == b'>' => break,
                 Tree1::Whitespace(_) => {
                     iter.next().expect(peeked!());
                     continue
                 }
-                _ => Attr::scan(iter, buf)?,
+                _ => attrs.push(Attr::parse(iter, buf)?),
             }
         }
 
@@ -202,51 +450,153 @@ impl Element {
 
         span.spanned_into(iter.span());
 
-        Ok(Self { span, kind, tag: tag.into() })
+        Ok(Self { span, kind, tag, attrs })
+    }
+
+    /// returns value span of attribute with case insensitive key, quote is excluded
+    ///
+    /// attribute without value, like `disabled`, returns [`None`], use [`Element::has_attr`]
+    /// to check its presence
+    pub fn attr(&self, buf: &[u8], name: &[u8]) -> Option<Span> {
+        self.find_attr(name, buf)?.value.clone()
+    }
+
+    /// is attribute with case insensitive key present
+    pub fn has_attr(&self, buf: &[u8], name: &[u8]) -> bool {
+        self.find_attr(name, buf).is_some()
+    }
+
+    /// returns attribute with case insensitive key
+    pub fn get_attr(&self, buf: &[u8], name: &[u8]) -> Option<&Attr> {
+        self.find_attr(name, buf)
+    }
+
+    /// all attributes in source order
+    pub fn attrs(&self) -> &[Attr] {
+        &self.attrs
+    }
+
+    /// classified tag name, see [`TagName`]
+    pub fn tag(&self, buf: &[u8]) -> TagName {
+        TagName::classify(self.tag.span(), buf)
+    }
+
+    /// returns the nearest `lang` attribute value, see [`Element::effective_attr`]
+    pub fn effective_lang<'r>(&self, ancestors: &[&Element], buf: &'r [u8]) -> Option<&'r str> {
+        self.effective_attr(b"lang", ancestors, buf)
+    }
+
+    /// returns the nearest `dir` attribute value, see [`Element::effective_attr`]
+    pub fn effective_dir<'r>(&self, ancestors: &[&Element], buf: &'r [u8]) -> Option<&'r str> {
+        self.effective_attr(b"dir", ancestors, buf)
+    }
+
+    /// returns the nearest inherited attribute value, looking up current element first
+    ///
+    /// `ancestors` is ordered from the root, so the parent element is the last
+    ///
+    /// attribute without value returns an empty string, and invalid utf8 value returns [`None`]
+    fn effective_attr<'r>(&self, name: &[u8], ancestors: &[&Element], buf: &'r [u8]) -> Option<&'r str> {
+        let attr = std::iter::once(self)
+            .chain(ancestors.iter().rev().copied())
+            .find_map(|element|element.find_attr(name, buf))?;
+        match &attr.value {
+            Some(value) => std::str::from_utf8(value.evaluate(buf)).ok(),
+            None => Some(""),
+        }
+    }
+
+    /// find attribute by case insensitive key
+    fn find_attr(&self, name: &[u8], buf: &[u8]) -> Option<&Attr> {
+        self.attrs.iter().find(|attr|attr.key.evaluate(buf).eq_ignore_ascii_case(name))
     }
 }
 
-pub struct Attr;
+/// html attribute, `key="value"` or `key`
+#[derive(Debug)]
+pub struct Attr {
+    key: Span,
+    /// quote is excluded
+    value: Option<Span>,
+    span: Span,
+}
 
 impl Attr {
+    /// the whole attribute from the start of the key to the end of the value
+    ///
+    /// whitespaces around `=` and the original quoting are included
+    pub fn raw_span(&self) -> Span {
+        self.span.clone()
+    }
+
+    /// is attribute without value, like `disabled`
+    ///
+    /// note that `disabled=""` has empty value and is not considered boolean
+    pub fn is_boolean(&self) -> bool {
+        self.value.is_none()
+    }
+
+    /// returns raw value, entities are not decoded, see [`Attr::value_decoded`]
+    ///
+    /// attribute without value or invalid utf8 value returns [`None`]
+    pub fn value_str<'r>(&self, buf: &'r [u8]) -> Option<&'r str> {
+        std::str::from_utf8(self.value.as_ref()?.evaluate(buf)).ok()
+    }
+
+    /// returns value with character references decoded, see [`entity::decode`]
+    pub fn value_decoded<'r>(&self, buf: &'r [u8]) -> Option<std::borrow::Cow<'r, str>> {
+        self.value_str(buf).map(entity::decode)
+    }
+
     /// consume iterator of one attribute
-    fn scan(iter: &mut Peekable1<4>, buf: &[u8]) -> Result<()> {
+    fn parse(iter: &mut Peekable1<4>, buf: &[u8]) -> Result<Self> {
         // key
-        loop {
+        let key = loop {
             match next!(iter) {
-                Tree1::Ident(_) => break,
-                Tree1::Whitespace(_) => continue,
-                Tree1::Punct(_) => return Err(Error::new(iter.span(), "expected an identifier")),
+                Tree1::Ident(key) => break key.span(),
+                Tree1::Whitespace(_) |
+                Tree1::Newline(_) => continue,
+                Tree1::Punct(_) |
+                Tree1::Comment(_) |
+                Tree1::Literal(_) => return Err(Error::new(iter.span(), "expected an identifier")),
             }
-        }
+        };
+
+        let mut span = key.clone();
 
         // eq
         loop {
             match peek!(iter) {
-                Tree1::Punct(punct) if punct.evaluate(buf)[0] == b'=' => {
+                Tree1::Punct(punct) if punct.as_byte(buf) == b'=' => {
                     iter.next().expect(peeked!());
                     break
                 }
-                Tree1::Whitespace(_) => {
+                Tree1::Whitespace(_) |
+                Tree1::Newline(_) => {
                     iter.next().expect(peeked!());
                     continue
                 }
-                Tree1::Punct(punct) if punct.evaluate(buf)[0] == b'>' => return Ok(()),
-                Tree1::Punct(_) => return Err(Error::new(iter.span(), "expected `=` or `>`")),
-                Tree1::Ident(_) => return Ok(()),
+                Tree1::Punct(punct) if punct.as_byte(buf) == b'>' => return Ok(Self { key, value: None, span }),
+                Tree1::Punct(_) |
+                Tree1::Comment(_) |
+                Tree1::Literal(_) => return Err(Error::new(iter.span(), "expected `=` or `>`")),
+                Tree1::Ident(_) => return Ok(Self { key, value: None, span }),
             }
         }
 
         // open quote
         loop {
             match peek!(iter) {
-                Tree1::Punct(punct) if punct.evaluate(buf)[0] == b'"' => {
+                Tree1::Punct(punct) if punct.as_byte(buf) == b'"' => {
                     iter.next().expect(peeked!());
                     break
                 }
                 Tree1::Ident(_) |
-                Tree1::Punct(_) => return Err(Error::new(iter.span(), "expected `\"`")),
-                Tree1::Whitespace(_) => {
+                Tree1::Punct(_) |
+                Tree1::Comment(_) |
+                Tree1::Literal(_) => return Err(Error::new(iter.span(), "expected `\"`")),
+                Tree1::Whitespace(_) |
+                Tree1::Newline(_) => {
                     iter.next().expect(peeked!());
                     continue
                 }
@@ -254,14 +604,22 @@ impl Attr {
         }
 
         // close quote
+        let open = iter.span();
+        let (line, col) = open.line_col();
+        let mut value = Span::new(open.offset() + 1, 0, line, col + 1);
         loop {
             match next!(iter) {
-                Tree1::Punct(punct) if punct.evaluate(buf)[0] == b'"' => break,
-                _ => continue,
+                Tree1::Punct(punct) if punct.as_byte(buf) == b'"' => break,
+                tree => {
+                    let end = tree.span();
+                    value = Span::new(value.offset(), end.offset() + end.len() - value.offset(), line, col + 1);
+                }
             }
         }
 
-        Ok(())
+        span.spanned_into(iter.span());
+
+        Ok(Self { key, value: Some(value), span })
     }
 }
 
@@ -271,13 +629,33 @@ pub struct Text {
 }
 
 impl Text {
+    /// returns visible text, whitespace runs collapsed into a single space and trimmed
+    ///
+    /// `preserve` returns the original text instead, for `<pre>` like context, character
+    /// references are not decoded, see [`Text::decoded`]
+    pub fn normalized(&self, buf: &[u8], preserve: bool) -> String {
+        let text = self.evaluate(buf);
+        if preserve {
+            return String::from_utf8_lossy(text).into_owned();
+        }
+        let text = diff::collapse(text).collect::<Vec<_>>();
+        String::from_utf8_lossy(&text).into_owned()
+    }
+
+    /// returns text with character references decoded, see [`entity::decode`]
+    ///
+    /// invalid utf8 text returns [`None`]
+    pub fn decoded<'r>(&self, buf: &'r [u8]) -> Option<std::borrow::Cow<'r, str>> {
+        std::str::from_utf8(self.evaluate(buf)).ok().map(entity::decode)
+    }
+
     fn parse(iter: &mut Peekable1<4>, buf: &[u8]) -> Result<Self> {
         let tree = iter.next().expect(peeked!());
         let mut span = tree.span();
 
         loop {
             match iter.peek() {
-                Some(Tree1::Punct(punct)) if punct.evaluate(buf)[0] == b'<' => break,
+                Some(Tree1::Punct(punct)) if punct.as_byte(buf) == b'<' => break,
                 Some(_) => { iter.next().expect(peeked!()); },
                 None => break,
             }
@@ -290,17 +668,20 @@ impl Text {
 }
 
 pub mod tokenizer {
-    use crate::{error::Result, Comment, Element, Peekable1, SyntaxTree, Text, Tokenizer1, DOCTYPE};
+    use ::tokenizer::span::Spanned;
+    use crate::{error::{Error, Result}, Comment, Element, Peekable1, SyntaxTree, Text, Tokenizer1, Tree1, DOCTYPE};
 
     /// tokenizer iterator are fallible
     ///
     /// when error occurs, its more likely the parsing did not proceed fully. Calling next in this
     /// state will continue parsing and may resulting in premature parsing, so its recommended to
-    /// terminate iterator when error occurs.
+    /// terminate iterator when error occurs. To keep parsing after an error, use
+    /// [`parse_recovering`].
     ///
     /// we can use [`std::result::Result`]'s [`std::iter::FromIterator`] when `collect`ing
     ///
     /// ```
+    /// # use html_parser::{tokenizer::Tokenizer, error::Result, SyntaxTree};
     /// let tokenizer = Tokenizer::new(b"source");
     /// let result: Result<Vec<SyntaxTree>> = tokenizer.collect();
     /// ```
@@ -308,12 +689,71 @@ pub mod tokenizer {
     pub struct Tokenizer<'r> {
         buf: &'r [u8],
         iter: Peekable1<'r,4>,
+        strict: bool,
+        /// any tree other than comment or whitespace text has been emitted
+        content_seen: bool,
     }
 
     impl<'r> Tokenizer<'r> {
         pub fn new(src: &'r [u8]) -> Self {
-            Self { buf: src, iter: Tokenizer1::new(src).peekable_tokens() }
+            Self { buf: src, iter: Tokenizer1::new(src).peekable_tokens(), strict: false, content_seen: false }
+        }
+
+        /// error when `DOCTYPE` appears after an element, a non whitespace text, or another
+        /// `DOCTYPE`
+        ///
+        /// by default, `DOCTYPE` is accepted anywhere
+        ///
+        /// ```
+        /// # use html_parser::{tokenizer::Tokenizer, error::Result, SyntaxTree};
+        /// let src = b"<!DOCTYPE html>\n<p></p><!DOCTYPE html>";
+        /// assert!(Tokenizer::new(src).collect::<Result<Vec<_>>>().is_ok());
+        /// assert!(Tokenizer::new(src).strict().collect::<Result<Vec<_>>>().is_err());
+        /// ```
+        pub fn strict(mut self) -> Self {
+            self.strict = true;
+            self
+        }
+
+        /// skip tokens until the next `<`, which is the next plausible start of a tree
+        fn recover(&mut self) {
+            loop {
+                match self.iter.peek() {
+                    Some(Tree1::Punct(punct)) if punct.as_byte(self.buf) == b'<' => break,
+                    Some(_) => { self.iter.next(); },
+                    None => break,
+                }
+            }
+        }
+    }
+
+    /// parse the whole source, collecting all errors instead of stopping at the first one
+    ///
+    /// when a tree fails to parse, the error is recorded and the rest of the tree is skipped
+    /// until the next `<`
+    ///
+    /// ```
+    /// # use html_parser::tokenizer::parse_recovering;
+    /// let (trees, errors) = parse_recovering(b"<a =x><p>text</p>");
+    /// assert_eq!(trees.len(), 3);
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    pub fn parse_recovering(src: &[u8]) -> (Vec<SyntaxTree>, Vec<Error>) {
+        let mut tokenizer = Tokenizer::new(src);
+        let mut trees = vec![];
+        let mut errors = vec![];
+
+        while let Some(result) = tokenizer.next() {
+            match result {
+                Ok(tree) => trees.push(tree),
+                Err(err) => {
+                    errors.push(err);
+                    tokenizer.recover();
+                }
+            }
         }
+
+        (trees, errors)
     }
 
     macro_rules! nerr {
@@ -332,8 +772,13 @@ pub mod tokenizer {
             let tree = match () {
                 _ if Comment::peek(&mut self.iter, self.buf)
                     => SyntaxTree::Comment(nerr!(Comment::parse(&mut self.iter, self.buf))),
-                _ if DOCTYPE::peek(&mut self.iter, self.buf)
-                    => SyntaxTree::DOCTYPE(nerr!(DOCTYPE::parse(&mut self.iter, self.buf))),
+                _ if DOCTYPE::peek(&mut self.iter, self.buf) => {
+                    let doctype = nerr!(DOCTYPE::parse(&mut self.iter, self.buf));
+                    if self.strict && self.content_seen {
+                        return Some(Err(Error::new(doctype.span(), "unexpected DOCTYPE after content")));
+                    }
+                    SyntaxTree::DOCTYPE(doctype)
+                }
                 _ if Element::peek(&mut self.iter, self.buf)
                     => SyntaxTree::Element(nerr!(Element::parse(&mut self.iter, self.buf))),
                 _ => if self.iter.peek().is_some() {
@@ -343,6 +788,12 @@ pub mod tokenizer {
                 },
             };
 
+            match &tree {
+                SyntaxTree::Comment(_) => {}
+                SyntaxTree::Text(text) if text.evaluate(self.buf).trim_ascii().is_empty() => {}
+                _ => self.content_seen = true,
+            }
+
             Some(Ok(tree))
         }
     }
@@ -377,6 +828,166 @@ pub mod error {
     }
 }
 
+pub mod entity {
+    //! html character reference decoding
+    use std::borrow::Cow;
+
+    /// decode character references, like `&amp;`, `&#60;` and `&#x3C;`
+    ///
+    /// only `amp`, `lt`, `gt`, `quot` and `apos` named references are supported, enable
+    /// `full-entities` feature to support all WHATWG named references, including `nbsp`
+    ///
+    /// unknown or malformed reference is kept as is, and `&#0;` is replaced with U+FFFD
+    ///
+    /// ```
+    /// use html_parser::entity::decode;
+    /// assert_eq!(decode("a &amp; &#98; &#x63; &foo;"), "a & b c &foo;");
+    /// ```
+    pub fn decode(src: &str) -> Cow<'_, str> {
+        if !src.contains('&') {
+            return Cow::Borrowed(src);
+        }
+
+        let mut out = String::with_capacity(src.len());
+        let mut rest = src;
+        while let Some(amp) = rest.find('&') {
+            out.push_str(&rest[..amp]);
+            rest = &rest[amp..];
+            let end = rest.bytes().skip(1).take(MAX_NAME_LEN + 1).position(|b|b == b';').map(|i|i + 1);
+            match end.filter(|end|reference(&rest[1..*end], &mut out)) {
+                Some(end) => rest = &rest[end + 1..],
+                None => {
+                    out.push('&');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+
+        Cow::Owned(out)
+    }
+
+    /// longest reference name between `&` and `;`, which is `CounterClockwiseContourIntegral`
+    ///
+    /// bounding the search for `;` keep decoding linear when `&` is not a reference
+    const MAX_NAME_LEN: usize = 31;
+
+    /// resolve reference name between `&` and `;` into `out`, return false if unknown
+    fn reference(name: &str, out: &mut String) -> bool {
+        if let Some(num) = name.strip_prefix('#') {
+            let code = match num.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => num.parse().ok(),
+            };
+            let code = code.map(|code|if code == 0 { 0xFFFD } else { code });
+            return match code.and_then(char::from_u32) {
+                Some(ch) => {
+                    out.push(ch);
+                    true
+                }
+                None => false,
+            };
+        }
+
+        match named(name) {
+            Some(value) => {
+                out.push_str(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[cfg(not(feature = "full-entities"))]
+    fn named(name: &str) -> Option<&'static str> {
+        match name {
+            "amp" => Some("&"),
+            "lt" => Some("<"),
+            "gt" => Some(">"),
+            "quot" => Some("\""),
+            "apos" => Some("'"),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "full-entities")]
+    fn named(name: &str) -> Option<&'static str> {
+        let table = crate::entity_table::ENTITIES;
+        let index = table.binary_search_by(|(key, _)|(*key).cmp(name)).ok()?;
+        Some(table[index].1)
+    }
+}
+
+pub mod diff {
+    //! structural comparison of parsed documents
+    use ::tokenizer::span::Spanned;
+    use crate::{Element, SyntaxTree};
+
+    /// compare two documents structurally
+    ///
+    /// text that only contains whitespace is ignored, and whitespace runs in the rest of the
+    /// tokens are collapsed into a single space and trimmed, so `<div> <p>x</p> </div>` is
+    /// equivalent to `<div><p>x</p></div>`
+    ///
+    /// attribute values are compared verbatim, so `<p id="a b">` is not equivalent to
+    /// `<p id="a  b">`
+    ///
+    /// note that this is stricter than browser rendering, whitespace only text between inline
+    /// elements is also ignored
+    pub fn nodes_equivalent(a: &[SyntaxTree], b: &[SyntaxTree], buf_a: &[u8], buf_b: &[u8]) -> bool {
+        let mut a = a.iter().filter(|tree|is_significant(tree, buf_a));
+        let mut b = b.iter().filter(|tree|is_significant(tree, buf_b));
+
+        loop {
+            match (a.next(), b.next()) {
+                (Some(a), Some(b)) => if !tree_equivalent(a, b, buf_a, buf_b) {
+                    return false
+                },
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+
+    fn tree_equivalent(a: &SyntaxTree, b: &SyntaxTree, buf_a: &[u8], buf_b: &[u8]) -> bool {
+        let same_kind = matches!(
+            (a,b),
+            (SyntaxTree::Comment(_), SyntaxTree::Comment(_)) |
+            (SyntaxTree::DOCTYPE(_), SyntaxTree::DOCTYPE(_)) |
+            (SyntaxTree::Element(_), SyntaxTree::Element(_)) |
+            (SyntaxTree::Text(_), SyntaxTree::Text(_))
+        );
+        let same_attrs = match (a, b) {
+            (SyntaxTree::Element(a), SyntaxTree::Element(b)) => attrs_equal(a, b, buf_a, buf_b),
+            _ => true,
+        };
+        same_kind && same_attrs && collapse(a.evaluate(buf_a)).eq(collapse(b.evaluate(buf_b)))
+    }
+
+    /// attribute values are significant, including its whitespace
+    fn attrs_equal(a: &Element, b: &Element, buf_a: &[u8], buf_b: &[u8]) -> bool {
+        a.attrs.len() == b.attrs.len() && a.attrs.iter().zip(&b.attrs).all(|(a,b)|{
+            a.value.as_ref().map(|e|e.evaluate(buf_a)) == b.value.as_ref().map(|e|e.evaluate(buf_b))
+        })
+    }
+
+    fn is_significant(tree: &SyntaxTree, buf: &[u8]) -> bool {
+        match tree {
+            SyntaxTree::Text(text) => !text.evaluate(buf).iter().all(u8::is_ascii_whitespace),
+            _ => true,
+        }
+    }
+
+    /// trim and collapse whitespace runs into a single space
+    pub(crate) fn collapse(buf: &[u8]) -> impl Iterator<Item = u8> + '_ {
+        buf
+            .split(u8::is_ascii_whitespace)
+            .filter(|word|!word.is_empty())
+            .enumerate()
+            .flat_map(|(i,word)|(i != 0).then_some(b' ').into_iter().chain(word.iter().copied()))
+    }
+}
+
 mod impls {
     use super::*;
 
@@ -404,6 +1015,12 @@ mod impls {
         }
     }
 
+    impl Spanned for Attr {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
     impl Spanned for Ident {
         fn span(&self) -> Span {
             self.span.clone()
@@ -423,3 +1040,317 @@ mod impls {
 
 }
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_text_span() {
+        let src = b"  hello<b>";
+        let mut iter = Tokenizer1::new(src).peekable_tokens();
+        let text = Text::parse(&mut iter, src).unwrap();
+        assert_eq!(text.evaluate(src), b"  hello");
+
+        let src = b"<p>a &amp; b</p>";
+        let trees = tokenizer::Tokenizer::new(src).collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(trees[1].evaluate(src), b"a &amp; b");
+    }
+
+    #[test]
+    fn test_text_normalized() {
+        let src = b"  a \n\t b  <p>";
+        let mut iter = Tokenizer1::new(src).peekable_tokens();
+        let text = Text::parse(&mut iter, src).unwrap();
+        assert_eq!(text.normalized(src, false), "a b");
+        assert_eq!(text.normalized(src, true), "  a \n\t b  ");
+    }
+
+    #[test]
+    fn test_text_decoded() {
+        let src = b"a&lt;b &bogus; &nbsp;<p>";
+        let mut iter = Tokenizer1::new(src).peekable_tokens();
+        let text = Text::parse(&mut iter, src).unwrap();
+        let decoded = text.decoded(src).unwrap();
+        assert!(decoded.starts_with("a<b &bogus; "));
+
+        #[cfg(feature = "full-entities")]
+        assert_eq!(decoded, "a<b &bogus; \u{a0}");
+        #[cfg(not(feature = "full-entities"))]
+        assert_eq!(decoded, "a<b &bogus; &nbsp;");
+    }
+
+    #[test]
+    fn test_decode_bounded() {
+        use entity::decode;
+        assert_eq!(decode("&#0;&#x0;"), "\u{fffd}\u{fffd}");
+        assert_eq!(decode("&&amp;"), "&&");
+        assert_eq!(decode("& a; &amp;"), "& a; &");
+
+        let far = format!("&{};", "a".repeat(32));
+        assert_eq!(decode(&far), far);
+        let numeric = format!("&#{}60;", "0".repeat(27));
+        assert_eq!(decode(&numeric), "<");
+    }
+
+    #[cfg(feature = "full-entities")]
+    #[test]
+    fn test_full_entities() {
+        use entity::decode;
+        assert_eq!(decode("&mdash;&NotEqualTilde;&AElig;"), "\u{2014}\u{2242}\u{338}\u{c6}");
+        assert!(entity_table::ENTITIES.windows(2).all(|e|e[0].0 < e[1].0));
+    }
+
+    #[test]
+    fn test_comment_unterminated() {
+        let src = b"<p><!-- never closes -";
+        let err = tokenizer::Tokenizer::new(src).collect::<Result<Vec<_>>>().unwrap_err();
+        assert_eq!(err.to_string(), "[1:4] unterminated comment");
+
+        let src = b"<!-- a -- b --->x";
+        let mut iter = Tokenizer1::new(src).peekable_tokens();
+        let comment = Comment::parse(&mut iter, src).unwrap();
+        assert_eq!(comment.content(src), b" a -- b -");
+        assert_eq!(iter.next().unwrap().evaluate(src), b"x");
+    }
+
+    #[test]
+    fn test_comment_content() {
+        let src = b"<!-- [if IE] -- x -->";
+        let mut iter = Tokenizer1::new(src).peekable_tokens();
+        let comment = Comment::parse(&mut iter, src).unwrap();
+        assert_eq!(comment.content(src), b" [if IE] -- x ");
+
+        let src = b"<!---->";
+        let mut iter = Tokenizer1::new(src).peekable_tokens();
+        let comment = Comment::parse(&mut iter, src).unwrap();
+        assert_eq!(comment.evaluate(src), src);
+        assert_eq!(comment.content(src), b"");
+    }
+
+    #[test]
+    fn test_tree_attrs() {
+        let src = br#"<a href="/" hidden>link</a>"#;
+        let trees = tokenizer::Tokenizer::new(src).collect::<Result<Vec<_>>>().unwrap();
+
+        assert_eq!(trees[0].tag_name(src), Some("a"));
+        let attrs = trees[0].attrs().unwrap();
+        assert_eq!(attrs.len(), 2);
+        assert_eq!(attrs[0].value_str(src), Some("/"));
+        assert!(attrs[1].is_boolean());
+
+        assert!(matches!(trees[1], SyntaxTree::Text(_)));
+        assert!(trees[1].attrs().is_none());
+        assert!(trees[1].tag_name(src).is_none());
+
+        assert_eq!(trees[2].tag_name(src), Some("a"));
+        assert!(trees[2].attrs().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tag_name() {
+        let src = b"<DIV><br><script></script><custom-el></custom-el>";
+        let trees = tokenizer::Tokenizer::new(src).collect::<Result<Vec<_>>>().unwrap();
+        let tags = trees.iter().map(|tree|match tree {
+            SyntaxTree::Element(element) => element.tag(src),
+            _ => panic!("expected element"),
+        }).collect::<Vec<_>>();
+
+        assert_eq!(tags[0], TagName::Div);
+        assert_eq!(tags[0].as_str(), Some("div"));
+        assert_eq!(tags[1], TagName::Br);
+        assert!(tags[1].is_void());
+        assert!(!tags[1].is_raw_text());
+        assert_eq!(tags[2], TagName::Script);
+        assert!(tags[2].is_raw_text());
+        assert!(!tags[2].is_void());
+
+        let TagName::Other(span) = &tags[4] else {
+            panic!("expected custom element")
+        };
+        assert_eq!(span.evaluate(src), b"custom-el");
+        assert_eq!(tags[4].as_str(), None);
+        assert_eq!(trees[5].tag_name(src), Some("custom-el"));
+    }
+
+    #[test]
+    fn test_strict_doctype() {
+        let src = b"<div></div><!DOCTYPE html>";
+        let trees = tokenizer::Tokenizer::new(src).collect::<Result<Vec<_>>>().unwrap();
+        assert!(matches!(trees[2], SyntaxTree::DOCTYPE(_)));
+
+        let err = tokenizer::Tokenizer::new(src).strict().collect::<Result<Vec<_>>>().unwrap_err();
+        assert_eq!(err.to_string(), "[1:12] unexpected DOCTYPE after content");
+
+        let src = b"<!-- a -->\n<!DOCTYPE html>\n<div></div>";
+        assert!(tokenizer::Tokenizer::new(src).strict().collect::<Result<Vec<_>>>().is_ok());
+
+        let src = b"<!DOCTYPE html><!DOCTYPE html>";
+        assert!(tokenizer::Tokenizer::new(src).strict().collect::<Result<Vec<_>>>().is_err());
+    }
+
+    #[test]
+    fn test_doctype() {
+        let src = b"<!doctype html>";
+        let mut iter = Tokenizer1::new(src).peekable_tokens();
+        let doctype = DOCTYPE::parse(&mut iter, src).unwrap();
+        assert_eq!(doctype.name(src), Some("html"));
+        assert!(doctype.dtd().is_none());
+        assert!(doctype.is_html5(src));
+
+        let src = br#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01//EN" "http://www.w3.org/TR/html4/strict.dtd" >"#;
+        let mut iter = Tokenizer1::new(src).peekable_tokens();
+        let doctype = DOCTYPE::parse(&mut iter, src).unwrap();
+        assert_eq!(doctype.evaluate(src), &src[..]);
+        assert_eq!(doctype.name(src), Some("HTML"));
+        assert_eq!(
+            doctype.dtd().unwrap().evaluate(src),
+            br#"PUBLIC "-//W3C//DTD HTML 4.01//EN" "http://www.w3.org/TR/html4/strict.dtd""#
+        );
+        assert!(!doctype.is_html5(src));
+    }
+
+    #[test]
+    fn test_debug_with() {
+        let src = b"<!-- x -->\n<p id=\"a\">";
+        let trees = tokenizer::Tokenizer::new(src).collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(format!("{:?}", trees[0].debug(src)), r#"Comment("<!-- x -->" @ 1:1)"#);
+        assert_eq!(format!("{:?}", trees[2].debug(src)), r#"Element("<p id=\"a\">" @ 2:1)"#);
+    }
+
+    #[test]
+    fn test_attr_boolean_and_decoded() {
+        let src = br#"<input disabled value="&amp;&lt;b&#62;">"#;
+        let mut iter = Tokenizer1::new(src).peekable_tokens();
+        let input = Element::parse(&mut iter, src).unwrap();
+        assert_eq!(input.attrs().len(), 2);
+
+        let disabled = input.get_attr(src, b"disabled").unwrap();
+        assert!(disabled.is_boolean());
+        assert_eq!(disabled.value_str(src), None);
+
+        let value = input.get_attr(src, b"VALUE").unwrap();
+        assert!(!value.is_boolean());
+        assert_eq!(value.value_str(src), Some("&amp;&lt;b&#62;"));
+        assert_eq!(value.value_decoded(src).as_deref(), Some("&<b>"));
+    }
+
+    #[test]
+    fn test_attr_raw_span() {
+        let src = br#"class = "a b" id>"#;
+        let mut iter = Tokenizer1::new(src).peekable_tokens();
+
+        let attr = Attr::parse(&mut iter, src).unwrap();
+        assert_eq!(attr.evaluate(src), br#"class = "a b""#);
+        assert_eq!(attr.raw_span(), attr.span());
+
+        let attr = Attr::parse(&mut iter, src).unwrap();
+        assert_eq!(attr.evaluate(src), b"id");
+    }
+
+    #[test]
+    fn test_nodes_equivalent() {
+        use crate::{diff::nodes_equivalent, tokenizer::Tokenizer};
+
+        let parse = |src| Tokenizer::new(src).collect::<Result<Vec<_>>>().unwrap();
+
+        let a = b"<div> <p>x</p> </div>";
+        let b = b"<div><p>x</p></div>";
+        assert!(nodes_equivalent(&parse(a), &parse(b), a, b));
+
+        let c = b"<div><p>y</p></div>";
+        assert!(!nodes_equivalent(&parse(a), &parse(c), a, c));
+
+        let d = b"<div><p>x</p>";
+        assert!(!nodes_equivalent(&parse(a), &parse(d), a, d));
+
+        let e = b"<p id=\"a b\">x</p>";
+        let f = b"<p\n  id=\"a b\">x</p>";
+        assert!(nodes_equivalent(&parse(e), &parse(f), e, f));
+
+        let g = b"<p id=\"a  b\">x</p>";
+        assert!(!nodes_equivalent(&parse(e), &parse(g), e, g));
+    }
+
+    #[test]
+    fn test_parse_recovering() {
+        use crate::tokenizer::parse_recovering;
+
+        let src = b"<a =x><p></p><b !><br>";
+        let (trees, errors) = parse_recovering(src);
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].to_string(), "[1:4] expected an identifier");
+        assert_eq!(errors[1].to_string(), "[1:17] expected an identifier");
+
+        let trees = trees.iter().map(|e|e.evaluate(src)).collect::<Vec<_>>();
+        assert_eq!(trees, [&b"<p>"[..], b"</p>", b"<br>"]);
+    }
+
+    #[test]
+    fn test_effective_lang() {
+        let src = br#"<html lang="en"><body dir="rtl"><p LANG="id"><b>x</b></p></body></html>"#;
+        let trees = tokenizer::Tokenizer::new(src).collect::<Result<Vec<_>>>().unwrap();
+        let elements = trees
+            .iter()
+            .filter_map(|tree|match tree {
+                SyntaxTree::Element(element) => Some(element),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let [html, body, p, b, ..] = elements[..] else {
+            panic!("expected 4 open elements")
+        };
+
+        assert_eq!(body.effective_lang(&[html], src), Some("en"));
+        assert_eq!(body.effective_dir(&[html], src), Some("rtl"));
+        assert_eq!(p.effective_lang(&[html, body], src), Some("id"));
+        assert_eq!(b.effective_lang(&[html, body, p], src), Some("id"));
+        assert_eq!(b.effective_dir(&[html, body, p], src), Some("rtl"));
+        assert_eq!(html.effective_dir(&[], src), None);
+    }
+
+    #[test]
+    fn test_extract_region() {
+        let src = b"<div>\n<!-- BEGIN a -->\n<p>x</p>\n<!--END a--><!-- BEGIN b --><!-- END b --></div>";
+
+        let region = extract_region(src, "a").unwrap();
+        assert_eq!(region.evaluate(src), b"\n<p>x</p>\n");
+        assert_eq!(region.line_col(), (2, 17));
+
+        let region = extract_region(src, "b").unwrap();
+        assert_eq!(region.evaluate(src), b"");
+
+        assert!(extract_region(src, "c").is_none());
+        assert!(extract_region(b"<!-- BEGIN a --><p>", "a").is_none());
+    }
+
+    #[test]
+    fn test_element_attr() {
+        let src = br#"<a HREF="x" download>"#;
+        let mut iter = Tokenizer1::new(src).peekable_tokens();
+        let element = Element::parse(&mut iter, src).unwrap();
+
+        let href = element.attr(src, b"href").unwrap();
+        assert_eq!(href.evaluate(src), b"x");
+        assert!(element.has_attr(src, b"href"));
+
+        assert!(element.attr(src, b"download").is_none());
+        assert!(element.has_attr(src, b"download"));
+
+        assert!(element.attr(src, b"title").is_none());
+        assert!(!element.has_attr(src, b"title"));
+    }
+
+    #[test]
+    fn test_stats() {
+        let src = b"<!DOCTYPE html><html><!-- nav --><body><p>a<br>b</p><p>c</p></body></html>";
+        let stats = stats(src).unwrap();
+        assert_eq!(stats, DocStats {
+            elements: 5,
+            text_nodes: 3,
+            comments: 1,
+            doctypes: 1,
+            max_depth: 3,
+        });
+    }
+}
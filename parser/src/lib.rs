@@ -1,11 +1,110 @@
 use error::{Error, ErrorKind, Result};
 use span::Span;
 
+pub use span::Spanned;
+
 // a parse-able token
 pub trait Parse where Self: Sized {
     fn parse(input: &mut Parser) -> Result<Self>;
 }
 
+/// implement [`Parse`] for a struct by parsing each field in order
+///
+/// each field is parsed with [`Parser::parse`], so whitespaces between fields are skipped
+///
+/// ```
+/// use parser::{parse_struct, token::{ByteLit, Ident, Phrase}, Parser};
+///
+/// struct KeyValue {
+///     key: Ident,
+///     _eq: ByteLit<b'='>,
+///     value: Phrase,
+/// }
+///
+/// parse_struct!(KeyValue { key: Ident, _eq: ByteLit<b'='>, value: Phrase });
+///
+/// let src = b"key=value";
+/// let kv = Parser::new(src).parse::<KeyValue>().unwrap();
+/// assert_eq!(kv.key.span.evaluate(src), b"key");
+/// assert_eq!(kv.value.span.evaluate(src), b"value");
+/// ```
+#[macro_export]
+macro_rules! parse_struct {
+    ($name:ident { $($field:ident: $ty:ty),* $(,)? }) => {
+        impl $crate::Parse for $name {
+            fn parse(input: &mut $crate::Parser) -> $crate::error::Result<Self> {
+                $(let $field = input.parse::<$ty>()?;)*
+                Ok(Self { $($field),* })
+            }
+        }
+    };
+}
+
+/// implement [`Parse`] for an enum by trying each variant in order
+///
+/// each variant is parsed with [`Parser::parse`], parser is rolled back between failed
+/// variants, if all variants fail, the last error is returned
+///
+/// ```
+/// use parser::{parse_enum, token::{Ident, LitInt, LitStr}, Parser};
+///
+/// enum Value {
+///     Int(LitInt),
+///     Str(LitStr),
+///     Ident(Ident),
+/// }
+///
+/// parse_enum!(Value { Int(LitInt), Str(LitStr), Ident(Ident) });
+///
+/// let value = Parser::new(b"42").parse::<Value>().unwrap();
+/// assert!(matches!(value, Value::Int(_)));
+/// ```
+#[macro_export]
+macro_rules! parse_enum {
+    ($name:ident { $($variant:ident($ty:ty)),+ $(,)? }) => {
+        impl $crate::Parse for $name {
+            fn parse(input: &mut $crate::Parser) -> $crate::error::Result<Self> {
+                let checkpoint = input.checkpoint();
+                let mut error = None;
+                $(
+                    match input.parse::<$ty>() {
+                        Ok(ok) => return Ok(Self::$variant(ok)),
+                        Err(err) => {
+                            error = Some(err);
+                            input.rollback(checkpoint.clone());
+                        }
+                    }
+                )+
+                Err(error.expect("at least one variant"))
+            }
+        }
+    };
+}
+
+/// try each alternative in order, returning the first success, see [`Parser::parse_any`]
+///
+/// each alternative is a function that map a [`Parse`] type into the result, like enum
+/// variant constructor, the parsed type is inferred from the function
+///
+/// ```
+/// use parser::{alt, token::{Ident, LitStr}, Parser};
+///
+/// enum Value {
+///     Str(LitStr),
+///     Ident(Ident),
+/// }
+///
+/// let mut input = Parser::new(b"foo");
+/// let value = alt!(input, Value::Str, Value::Ident).unwrap();
+/// assert!(matches!(value, Value::Ident(_)));
+/// ```
+#[macro_export]
+macro_rules! alt {
+    ($input:expr, $($f:expr),+ $(,)?) => {
+        $input.parse_any(&[$(|input: &mut $crate::Parser<'_>|input.parse_map($f)),+])
+    };
+}
+
 // a parse-able token
 pub trait Peek where Self: Sized {
     fn peek(input: &Parser) -> Result<bool>;
@@ -18,6 +117,7 @@ pub trait Peek where Self: Sized {
 /// - advance by byte, [`Parser::next`], [`Parser::next_as`]
 /// - advance by token, [`Parser::parse`]
 /// - peeking, [`Parser::peek`], [`Parser::peek_byte`]
+/// - backtracking, [`Parser::checkpoint`], [`Parser::rollback`]
 /// - utility, [`Parser::skip_whitespaces`]
 pub struct Parser<'r> {
     buf: &'r [u8],
@@ -40,6 +140,7 @@ impl<'r> Parser<'r> {
     }
 
     /// advance cursor forward by a byte
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Result<u8> {
         if self.len() == self.offset {
             return Err(self.eof());
@@ -51,7 +152,7 @@ impl<'r> Parser<'r> {
 
         if val == b'\n' {
             self.line += 1;
-            self.col = 1;
+            self.col = 0;
         } else {
             self.col += 1;
         }
@@ -73,7 +174,7 @@ impl<'r> Parser<'r> {
 
     /// keep calling [`Parser::next`] if whitespace found
     pub fn skip_whitespaces(&mut self) {
-        while let Ok(w) = self.peek_byte() {
+        while let Some(w) = self.buf.get(self.offset) {
             if w.is_ascii_whitespace() {
                 self.next().expect("peeked");
             } else {
@@ -82,6 +183,23 @@ impl<'r> Parser<'r> {
         }
     }
 
+    /// advance until byte `B` or EOF, `B` is not consumed
+    ///
+    /// can be used to resync after an error, see [`Parser::recover_to`]
+    pub fn skip_until<const B: u8>(&mut self) {
+        self.recover_to(&[B]);
+    }
+
+    /// advance until any of given bytes or EOF, the found byte is not consumed
+    pub fn recover_to(&mut self, bytes: &[u8]) {
+        while let Some(b) = self.buf.get(self.offset) {
+            if bytes.contains(b) {
+                break;
+            }
+            self.next().expect("peeked");
+        }
+    }
+
     /// call [`Parse`] for given type
     ///
     /// trim leading and trailing whitespaces, see [`Self::skip_whitespaces`]
@@ -94,6 +212,173 @@ impl<'r> Parser<'r> {
         Ok(res)
     }
 
+    /// [`Parser::parse`] for given type if [`Peek`] return true
+    ///
+    /// return [`None`] without consuming token otherwise, leading whitespaces are still skipped
+    pub fn parse_opt<T>(&mut self) -> Result<Option<T>> where T: Parse + Peek {
+        self.skip_whitespaces();
+        if !T::peek(self)? {
+            return Ok(None);
+        }
+        self.parse().map(Some)
+    }
+
+    /// [`Parser::parse`] for given type repeatedly while [`Peek`] return true
+    pub fn parse_while<T>(&mut self) -> Result<Vec<T>> where T: Parse + Peek {
+        let mut items = vec![];
+        while let Some(item) = self.parse_opt()? {
+            items.push(item);
+        }
+        Ok(items)
+    }
+
+    /// [`Parser::parse`] for given type repeatedly until `END` byte found, then consume it
+    ///
+    /// return empty list if `END` immediately found, and [`ErrorKind::ExpectEof`] if EOF
+    /// reached before `END`
+    pub fn parse_terminated<T, const END: u8>(&mut self) -> Result<Vec<T>> where T: Parse {
+        let mut items = vec![];
+        loop {
+            self.skip_whitespaces();
+            match self.remaining_slice().first() {
+                Some(byte) if byte == &END => break,
+                Some(_) => items.push(self.parse()?),
+                None if self.offset == 0 => return Err(Error::new(Span::unknown(), ErrorKind::ExpectEof(END))),
+                None => return Err(self.error(ErrorKind::ExpectEof(END))),
+            }
+        }
+        self.next_as::<END>()?;
+        Ok(items)
+    }
+
+    /// consume `open`, run `f` for the content, then require `close`
+    ///
+    /// whitespaces around the content are skipped, if `close` is not found after the content,
+    /// [`ErrorKind::Unclosed`] is returned pointing at `open`
+    pub fn delimited_by<T, F>(&mut self, open: u8, close: u8, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Parser<'r>) -> Result<T>,
+    {
+        self.skip_whitespaces();
+        match self.remaining_slice().first().copied() {
+            Some(byte) if byte == open => self.next().expect("peeked"),
+            Some(byte) => {
+                self.next().expect("peeked");
+                return Err(self.error(ErrorKind::ExpectFound(open, byte)));
+            }
+            None if self.offset == 0 => return Err(Error::new(Span::unknown(), ErrorKind::ExpectEof(open))),
+            None => return Err(self.error(ErrorKind::ExpectEof(open))),
+        };
+        let open_span = self.span();
+
+        self.skip_whitespaces();
+        let value = f(self)?;
+        self.skip_whitespaces();
+
+        match self.remaining_slice().first() {
+            Some(byte) if byte == &close => {
+                self.next().expect("peeked");
+                Ok(value)
+            }
+            _ => Err(self.error_at_span(open_span, ErrorKind::Unclosed(open, close))),
+        }
+    }
+
+    /// try each alternative in order, returning the first success
+    ///
+    /// parser is rolled back between failed alternatives, if all alternatives fail, the last
+    /// error is returned, see [`alt!`] macro for convenient alternatives of different types
+    ///
+    /// # Panic
+    ///
+    /// panic if `alts` is empty
+    pub fn parse_any<T>(&mut self, alts: &[fn(&mut Parser<'r>) -> Result<T>]) -> Result<T> {
+        let checkpoint = self.checkpoint();
+        let mut error = None;
+        for alt in alts {
+            match alt(self) {
+                Ok(ok) => return Ok(ok),
+                Err(err) => {
+                    error = Some(err);
+                    self.rollback(checkpoint.clone());
+                }
+            }
+        }
+        Err(error.expect("at least one alternative"))
+    }
+
+    /// [`Parser::parse`] for given type then map the result
+    pub fn parse_map<T, U, F>(&mut self, f: F) -> Result<U> where T: Parse, F: FnOnce(T) -> U {
+        self.parse::<T>().map(f)
+    }
+
+    /// [`Parser::parse`] for given type and return its source text
+    ///
+    /// return [`ErrorKind::Utf8`] if the text is not valid utf8
+    pub fn parse_str<T>(&mut self) -> Result<(T, &'r str)> where T: Parse + Spanned {
+        let token = self.parse::<T>()?;
+        let span = token.span();
+        match std::str::from_utf8(span.evaluate(self.buf)) {
+            Ok(text) => Ok((token, text)),
+            Err(err) => Err(Error::new(span, ErrorKind::Utf8(err))),
+        }
+    }
+
+    /// parse identifier and check if its eq to given keyword
+    ///
+    /// parser is not advanced when identifier does not match
+    pub fn expect_keyword(&mut self, kw: &[u8]) -> Result<Span> {
+        let checkpoint = self.checkpoint();
+        let ident = self.parse::<token::Ident>()?;
+        let found = ident.span.evaluate(self.buf);
+        if found != kw {
+            let kind = ErrorKind::ExpectKeyword(
+                String::from_utf8_lossy(kw).into_owned(),
+                String::from_utf8_lossy(found).into_owned(),
+            );
+            let err = Error::new(ident.span, kind);
+            self.rollback(checkpoint);
+            return Err(err);
+        }
+        Ok(ident.span)
+    }
+
+    /// save current position, use [`Parser::rollback`] to restore it
+    pub const fn checkpoint(&self) -> ParserCheckpoint {
+        ParserCheckpoint { offset: self.offset, line: self.line, col: self.col }
+    }
+
+    /// restore position saved by [`Parser::checkpoint`]
+    pub fn rollback(&mut self, checkpoint: ParserCheckpoint) {
+        let ParserCheckpoint { offset, line, col } = checkpoint;
+        (self.offset, self.line, self.col) = (offset, line, col);
+    }
+
+    /// iterate [`token::GenericToken`] until eof
+    ///
+    /// whitespaces are not skipped, they are yielded as [`token::GenericToken::Whitespace`]
+    pub fn tokens(&mut self) -> impl Iterator<Item = Result<token::GenericToken>> + use<'_, 'r> {
+        std::iter::from_fn(|| {
+            let token = match self.peek_byte().ok()? {
+                b'"' => token::LitStr::parse(self).map(token::GenericToken::LitStr),
+                b if b.is_ascii_whitespace() => {
+                    self.next().expect("peeked");
+                    let span = self.span();
+                    self.skip_whitespaces();
+                    Ok(token::GenericToken::Whitespace(span.into_spanned(&self.span())))
+                }
+                b if b.is_ascii_alphabetic() || b == &b'_' => {
+                    token::Ident::parse(self).map(token::GenericToken::Ident)
+                }
+                _ => {
+                    self.next().expect("peeked");
+                    Ok(token::GenericToken::Punct(self.span()))
+                }
+            };
+            Some(token)
+        })
+    }
+
     /// peek the next byte without advancing parser
     ///
     /// possible error is only [`ErrorKind::Eof`]
@@ -119,6 +404,16 @@ impl<'r> Parser<'r> {
         self.buf
     }
 
+    /// bytes that have not been consumed
+    pub const fn remaining_slice(&self) -> &[u8] {
+        self.buf.split_at(self.offset).1
+    }
+
+    /// bytes that have been consumed
+    pub const fn consumed_slice(&self) -> &[u8] {
+        self.buf.split_at(self.offset).0
+    }
+
     /// source buffer len
     pub const fn len(&self) -> usize {
         self.buf.len()
@@ -129,6 +424,38 @@ impl<'r> Parser<'r> {
         self.remaining() == 0
     }
 
+    /// count of consumed bytes
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// returns (line, column) of current state
+    ///
+    /// unlike [`Parser::span`], this does not panic before the first [`Parser::next`], where
+    /// it returns `(1, 0)`
+    pub const fn position(&self) -> (usize, usize) {
+        (self.line, self.col)
+    }
+
+    /// move cursor to given span start, same state as [`Parser::from_span`]
+    ///
+    /// span from [`Parser::offset`] and [`Parser::position`] restore exactly that state
+    pub fn seek(&mut self, span: Span) {
+        (self.offset, self.line, self.col) = (span.offset, span.line, span.col);
+    }
+
+    /// hand the remaining bytes to [`tokenizer`], advancing parser to EOF
+    ///
+    /// token spans are relative to the whole source, use [`Parser::seek`] with previously
+    /// saved [`Parser::offset`] and [`Parser::position`] to continue parsing instead
+    pub fn token_stream(&mut self) -> tokenizer::tokenizer::Tokenizer<'r> {
+        let line_start = self.buf[..self.offset].iter().rposition(|b|*b == b'\n').map_or(0, |i|i + 1);
+        let col = self.offset - line_start + 1;
+        let start = tokenizer::span::Span::new(self.offset, 1, self.line, col);
+        while self.next().is_ok() { }
+        tokenizer::tokenizer::Tokenizer::from_span(self.buf, start)
+    }
+
     /// create [`Span`] of current state
     pub const fn span(&self) -> Span {
         #[cfg(debug_assertions)]
@@ -147,6 +474,23 @@ impl<'r> Parser<'r> {
     pub const fn eof(&self) -> Error {
         Error::eof(self.span())
     }
+
+    /// create error at given span
+    ///
+    /// useful to point at previously parsed token instead of current position
+    pub const fn error_at_span(&self, span: Span, kind: ErrorKind) -> Error {
+        Error::new(span, kind)
+    }
+}
+
+/// saved [`Parser`] position
+///
+/// created by [`Parser::checkpoint`]
+#[derive(Debug, Clone)]
+pub struct ParserCheckpoint {
+    offset: usize,
+    line: usize,
+    col: usize,
 }
 
 impl<'r> From<&'r [u8]> for Parser<'r> {
@@ -165,7 +509,8 @@ pub mod span {
     /// the struct only contain 4 usize, which is cheap to clone
     ///
     /// use [`Span::evaluate`] to get actual value from given buffer
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Span {
         pub offset: usize,
         pub len: usize,
@@ -184,14 +529,47 @@ pub mod span {
             Self { offset: 0, len: 0, line: 0, col: 0 }
         }
 
+        /// returns (line, column) of the source
+        pub const fn line_col(&self) -> (usize,usize) {
+            (self.line,self.col)
+        }
+
         /// is current span unknown, see [`Self::unknown`]
         pub const fn is_unknown(&self) -> bool {
             self.offset == 0 && self.len == 0 && self.line == 0 && self.col == 0
         }
 
+        /// is current span zero length
+        ///
+        /// unlike [`Self::is_unknown`], empty span may point at a real offset, and evaluate to
+        /// an empty slice
+        pub const fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        /// return byte range of current span
+        pub const fn range(&self) -> std::ops::Range<usize> {
+            self.offset..self.offset + self.len
+        }
+
         /// return actual value from given source buffer
+        ///
+        /// # Panic
+        ///
+        /// panic if span is out of range of given buffer, use [`Span::try_evaluate`] to return
+        /// [`None`] instead
         pub fn evaluate<'r>(&self, buf: &'r [u8]) -> &'r [u8] {
-            &buf[self.offset..self.offset + self.len]
+            match self.try_evaluate(buf) {
+                Some(value) => value,
+                None => panic!("span {:?} out of range for buffer of len {}", self.range(), buf.len()),
+            }
+        }
+
+        /// return actual value from given source buffer, or [`None`] if span is out of range
+        ///
+        /// spans created against different buffer may be out of range
+        pub fn try_evaluate<'r>(&self, buf: &'r [u8]) -> Option<&'r [u8]> {
+            buf.get(self.range())
         }
 
         /// set length from current span to given span
@@ -206,6 +584,22 @@ pub mod span {
         }
     }
 
+    /// a value that has [`Span`]
+    pub trait Spanned {
+        /// returns this object span
+        fn span(&self) -> Span;
+        /// evaluate the actual value from source via span, see [`Span::evaluate`]
+        fn evaluate<'r>(&self, buf: &'r [u8]) -> &'r [u8] {
+            self.span().evaluate(buf)
+        }
+    }
+
+    impl Spanned for Span {
+        fn span(&self) -> Span {
+            self.clone()
+        }
+    }
+
 }
 
 pub mod error {
@@ -229,12 +623,33 @@ pub mod error {
     pub enum ErrorKind {
         /// unexpected eof
         Eof,
+        /// unexpected `_`
+        Unexpected(u8),
         /// expect `_`, found EOF
         ExpectEof(u8),
         /// expect `_`, found `_`
         ExpectFound(u8,u8),
         /// expect alphabetical, found `_`
         ExpectAlphabetic(u8),
+        /// expect digit, found `_`
+        ExpectDigit(u8),
+        /// expect keyword `_`, found `_`
+        ExpectKeyword(String,String),
+        /// `_` is not closed by `_`
+        Unclosed(u8,u8),
+        /// invalid escape sequence `\_`
+        InvalidEscape(u8),
+        /// empty character literal `''`
+        EmptyChar,
+        /// invalid utf8
+        Utf8(std::str::Utf8Error),
+        /// error occured while parsing `label`, see [`Error::context`]
+        Context {
+            label: &'static str,
+            source: Box<Error>,
+        },
+        /// error from other source without equivalent kind, see [`Error::from`]
+        Other(String),
     }
 
     impl Error {
@@ -252,12 +667,72 @@ pub mod error {
         pub const fn is_eof(&self) -> bool {
             matches!(self.kind,ErrorKind::Eof)
         }
+
+        /// wrap error with [`ErrorKind::Context`] describing what was being parsed
+        ///
+        /// the span is kept pointing at the original error
+        pub fn context(self, label: &'static str) -> Self {
+            Self::new(self.span.clone(), ErrorKind::Context { label, source: Box::new(self) })
+        }
+
+        /// render error message with the offending source line and `^` underline below the span
+        ///
+        /// ```text
+        /// [2:3] expected `x` found `d`
+        ///   |
+        /// 2 | bcd
+        ///   |   ^
+        /// ```
+        ///
+        /// only the message is rendered if span is unknown or out of range of given buffer
+        pub fn render(&self, buf: &[u8]) -> String {
+            let message = self.to_string();
+            if self.span.is_unknown() || self.span.offset > buf.len() {
+                return message;
+            }
+
+            let start = buf[..self.span.offset].iter().rposition(|b|*b == b'\n').map_or(0, |i|i + 1);
+            let end = buf[start..].iter().position(|b|*b == b'\n').map_or(buf.len(), |i|start + i);
+            let source = String::from_utf8_lossy(&buf[start..end]);
+
+            let (line,col) = self.span.line_col();
+            let gutter = " ".repeat(line.to_string().len());
+            let pad = " ".repeat(col.saturating_sub(1));
+            let carets = "^".repeat(self.span.len.clamp(1, (end - self.span.offset).max(1)));
+
+            format!("{message}\n{gutter} |\n{line} | {source}\n{gutter} | {pad}{carets}")
+        }
     }
 
     impl std::error::Error for Error { }
 
+    /// convert [`buf_iter::Error`] so `?` can be used in [`Parse`][crate::Parse] implementation
+    ///
+    /// kinds without equivalent is converted to [`ErrorKind::Other`] with its message
+    impl From<buf_iter::Error> for Error {
+        fn from(value: buf_iter::Error) -> Self {
+            use buf_iter::ErrorKind as Kind;
+            let buf_iter::Span { offset, len, line, col } = value.span;
+            let kind = match value.kind {
+                Kind::Eof => ErrorKind::Eof,
+                Kind::Unexpected(fd) => ErrorKind::Unexpected(fd),
+                Kind::ExpectEof(ex) => ErrorKind::ExpectEof(ex),
+                Kind::ExpectFound(ex, fd) => ErrorKind::ExpectFound(ex, fd),
+                Kind::ExpectAlphabetic(fd) => ErrorKind::ExpectAlphabetic(fd),
+                Kind::Unbalanced(open, close) => ErrorKind::Unclosed(open, close),
+                Kind::Utf8(err) => ErrorKind::Utf8(err),
+                kind => ErrorKind::Other(kind.to_string()),
+            };
+            Self::new(Span::new(offset, len, line, col), kind)
+        }
+    }
+
     impl std::fmt::Display for Error {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            if !self.span.is_unknown() {
+                let (line,col) = self.span.line_col();
+                write!(f, "[{line}:{col}] ")?;
+            }
             std::fmt::Display::fmt(&self.kind, f)
         }
     }
@@ -267,22 +742,54 @@ pub mod error {
             use std::fmt::Write;
             match self {
                 ErrorKind::Eof => f.write_str("unexpected EOF"),
-                ErrorKind::ExpectEof(fd) => {
-                    f.write_str("expected ")?;
+                ErrorKind::Unexpected(fd) => {
+                    f.write_str("unexpected `")?;
                     f.write_char(*fd as char)?;
-                    f.write_str("found EOF")
+                    f.write_char('`')
+                }
+                ErrorKind::ExpectEof(ex) => {
+                    f.write_str("expected `")?;
+                    f.write_char(*ex as char)?;
+                    f.write_str("` found EOF")
                 }
                 ErrorKind::ExpectFound(ex, fd) => {
-                    f.write_str("expected ")?;
+                    f.write_str("expected `")?;
                     f.write_char(*ex as char)?;
-                    f.write_str("found ")?;
-                    f.write_char(*fd as char)
+                    f.write_str("` found `")?;
+                    f.write_char(*fd as char)?;
+                    f.write_char('`')
                 }
                 ErrorKind::ExpectAlphabetic(fd) => {
-                    f.write_str("expected alphabetical, ")?;
-                    f.write_str("found ")?;
-                    f.write_char(*fd as char)
+                    f.write_str("expected alphabetical, found `")?;
+                    f.write_char(*fd as char)?;
+                    f.write_char('`')
+                }
+                ErrorKind::ExpectDigit(fd) => {
+                    f.write_str("expected digit, found `")?;
+                    f.write_char(*fd as char)?;
+                    f.write_char('`')
+                }
+                ErrorKind::ExpectKeyword(ex, fd) => {
+                    write!(f, "expected keyword `{ex}`, found `{fd}`")
+                }
+                ErrorKind::Unclosed(open, close) => {
+                    f.write_str("unclosed `")?;
+                    f.write_char(*open as char)?;
+                    f.write_str("`, expected `")?;
+                    f.write_char(*close as char)?;
+                    f.write_char('`')
                 }
+                ErrorKind::InvalidEscape(fd) => {
+                    f.write_str("invalid escape `\\")?;
+                    f.write_char(*fd as char)?;
+                    f.write_char('`')
+                }
+                ErrorKind::EmptyChar => f.write_str("empty character literal"),
+                ErrorKind::Utf8(err) => write!(f, "invalid utf8: {err}"),
+                ErrorKind::Context { label, source } => {
+                    write!(f, "while parsing {label}: {}", source.kind)
+                }
+                ErrorKind::Other(msg) => f.write_str(msg),
             }
         }
     }
@@ -292,7 +799,12 @@ pub mod error {
 pub mod token {
     //! built in tokens act as building block to create more tokens
 
-    use crate::{error::{ErrorKind, Result}, span::Span, Parse, Parser};
+    use crate::{error::{Error, ErrorKind, Result}, span::{Span, Spanned}, Parse, Parser, Peek};
+
+    /// peek next byte with given predicate, eof is not an error
+    fn peek_with(input: &Parser, f: impl Fn(&u8) -> bool) -> Result<bool> {
+        Ok(input.remaining_slice().first().is_some_and(f))
+    }
 
     /// parse identifier
     ///
@@ -328,9 +840,16 @@ pub mod token {
         }
     }
 
+    impl Peek for Ident {
+        fn peek(input: &Parser) -> Result<bool> {
+            peek_with(input, |byte|byte.is_ascii_alphabetic() || byte == &b'_')
+        }
+    }
+
     /// parse phrase
     ///
     /// phrase is sequence of anything but whitespace
+    #[derive(Debug)]
     pub struct Phrase {
         pub span: Span,
     }
@@ -361,7 +880,57 @@ pub mod token {
         }
     }
 
+    /// generic token, see [`Parser::tokens`]
+    #[derive(Debug)]
+    pub enum GenericToken {
+        /// see [`Ident`]
+        Ident(Ident),
+        /// see [`LitStr`]
+        LitStr(LitStr),
+        /// sequence of whitespace
+        Whitespace(Span),
+        /// a single byte other than above
+        Punct(Span),
+    }
+
+    /// literal integer
+    ///
+    /// sequence of ascii digit
+    #[derive(Debug)]
+    pub struct LitInt {
+        pub span: Span,
+    }
+
+    impl Parse for LitInt {
+        fn parse(input: &mut Parser) -> Result<Self> {
+            let lead = input.next()?;
+            let span = input.span();
+
+            if !lead.is_ascii_digit() {
+                return Err(input.error(ErrorKind::ExpectDigit(lead)));
+            }
+
+            loop {
+                match input.peek_byte() {
+                    Ok(byte) if byte.is_ascii_digit() => input.next().expect("peeked"),
+                    Ok(_) => break,
+                    Err(err) if err.is_eof() => break,
+                    Err(err) => return Err(err),
+                };
+            }
+
+            Ok(Self { span: span.into_spanned(&input.span()) })
+        }
+    }
+
+    impl Peek for LitInt {
+        fn peek(input: &Parser) -> Result<bool> {
+            peek_with(input, u8::is_ascii_digit)
+        }
+    }
+
     /// quoted literal string
+    #[derive(Debug)]
     pub struct LitStr {
         pub span: Span
     }
@@ -378,6 +947,82 @@ pub mod token {
         }
     }
 
+    impl Peek for LitStr {
+        fn peek(input: &Parser) -> Result<bool> {
+            peek_with(input, |byte|byte == &b'"')
+        }
+    }
+
+    /// single quoted character literal
+    ///
+    /// contains exactly one character or one escape sequence, `\n`, `\r`, `\t`, `\0`, `\\`,
+    /// `\'` or `\"`
+    #[derive(Debug)]
+    pub struct LitChar {
+        pub span: Span,
+    }
+
+    impl LitChar {
+        /// decode the character, escape sequence is resolved
+        pub fn value(&self, buf: &[u8]) -> Result<char> {
+            let lit = self.span.evaluate(buf);
+            let inner = &lit[1..lit.len() - 1];
+            if let [b'\\', esc] = inner {
+                return unescape(*esc)
+                    .ok_or_else(||Error::new(self.span.clone(), ErrorKind::InvalidEscape(*esc)));
+            }
+            match std::str::from_utf8(inner) {
+                Ok(ok) => ok.chars().next().ok_or(Error::new(self.span.clone(), ErrorKind::EmptyChar)),
+                Err(err) => Err(Error::new(self.span.clone(), ErrorKind::Utf8(err))),
+            }
+        }
+    }
+
+    /// resolve escape sequence byte after `\`
+    fn unescape(byte: u8) -> Option<char> {
+        match byte {
+            b'n' => Some('\n'),
+            b'r' => Some('\r'),
+            b't' => Some('\t'),
+            b'0' => Some('\0'),
+            b'\\' => Some('\\'),
+            b'\'' => Some('\''),
+            b'"' => Some('"'),
+            _ => None,
+        }
+    }
+
+    impl Parse for LitChar {
+        fn parse(input: &mut Parser) -> Result<Self> {
+            input.next_as::<b'\''>()?;
+            let span = input.span();
+
+            match input.next()? {
+                b'\'' => return Err(input.error(ErrorKind::EmptyChar)),
+                b'\\' => {
+                    let esc = input.next()?;
+                    if unescape(esc).is_none() {
+                        return Err(input.error(ErrorKind::InvalidEscape(esc)));
+                    }
+                }
+                // consume the rest of multi byte character
+                lead => for _ in 1..(lead.leading_ones() as usize).clamp(1, 4) {
+                    input.next()?;
+                },
+            }
+
+            input.next_as::<b'\''>()?;
+
+            Ok(Self { span: span.into_spanned(&input.span()) })
+        }
+    }
+
+    impl Peek for LitChar {
+        fn peek(input: &Parser) -> Result<bool> {
+            peek_with(input, |byte|byte == &b'\'')
+        }
+    }
+
     /// literal quoted string
     ///
     /// this token use nested parsing method, where it needs another token to parse the nested content
@@ -398,6 +1043,7 @@ pub mod token {
     ///     Ok(())
     /// }
     /// ```
+    #[derive(Debug)]
     pub struct Quoted {
         pub span: Span
     }
@@ -419,7 +1065,27 @@ pub mod token {
         }
     }
 
+    /// parse exactly one given byte
+    #[derive(Debug)]
+    pub struct ByteLit<const B: u8> {
+        pub span: Span,
+    }
+
+    impl<const B: u8> Parse for ByteLit<B> {
+        fn parse(input: &mut Parser) -> Result<Self> {
+            input.next_as::<B>()?;
+            Ok(Self { span: input.span() })
+        }
+    }
+
+    impl<const B: u8> Peek for ByteLit<B> {
+        fn peek(input: &Parser) -> Result<bool> {
+            peek_with(input, |byte|byte == &B)
+        }
+    }
+
     /// parse token surrounded by `{ .. }`
+    #[derive(Debug)]
     pub struct Braced {
         pub span: Span
     }
@@ -444,6 +1110,65 @@ pub mod token {
         }
     }
 
+    impl Spanned for Ident {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
+    impl Spanned for Phrase {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
+    impl Spanned for GenericToken {
+        fn span(&self) -> Span {
+            match self {
+                GenericToken::Ident(ident) => ident.span(),
+                GenericToken::LitStr(lit) => lit.span(),
+                GenericToken::Whitespace(span) => span.clone(),
+                GenericToken::Punct(span) => span.clone(),
+            }
+        }
+    }
+
+    impl Spanned for LitInt {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
+    impl Spanned for LitStr {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
+    impl Spanned for LitChar {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
+    impl Spanned for Quoted {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
+    impl<const B: u8> Spanned for ByteLit<B> {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
+    impl Spanned for Braced {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
 }
 
 #[cfg(debug_assertions)]
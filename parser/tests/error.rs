@@ -0,0 +1,116 @@
+use parser::{error::{Error, ErrorKind}, span::Span, token::{ByteLit, Ident, LitInt, LitStr}, Parser};
+
+#[test]
+fn error_display_line_col() {
+    let err = Error::new(Span::new(5, 1, 2, 4), ErrorKind::Eof);
+    assert_eq!(err.to_string(), "[2:4] unexpected EOF");
+
+    let err = Error::new(Span::unknown(), ErrorKind::Eof);
+    assert_eq!(err.to_string(), "unexpected EOF");
+}
+
+#[test]
+fn error_display_from_parser() {
+    let mut input = Parser::new(b"a\nbcd");
+    for _ in 0..4 {
+        input.next().unwrap();
+    }
+    let err = input.next_as::<b'x'>().unwrap_err();
+    assert!(err.to_string().starts_with("[2:3] "));
+}
+
+#[test]
+fn error_context() {
+    let mut input = Parser::new(b"key=value");
+    input.parse::<Ident>().unwrap();
+    input.next_as::<b'='>().unwrap();
+
+    let err = input
+        .parse::<LitStr>()
+        .map_err(|e|e.context("attribute value"))
+        .map_err(|e|e.context("element"))
+        .unwrap_err();
+
+    assert_eq!(err.span, Span::new(4, 1, 1, 5));
+    assert_eq!(
+        err.to_string(),
+        "[1:5] while parsing element: while parsing attribute value: expected `\"` found `v`"
+    );
+}
+
+#[test]
+fn error_render() {
+    let src = b"a\nbcd\ne";
+    let mut input = Parser::new(src);
+    for _ in 0..4 {
+        input.next().unwrap();
+    }
+    let err = input.next_as::<b'x'>().unwrap_err();
+    assert_eq!(
+        err.render(src),
+        "[2:3] expected `x` found `d`\n  |\n2 | bcd\n  |   ^"
+    );
+
+    let ident = Parser::new(b"foo").parse::<Ident>().unwrap();
+    let err = input.error_at_span(ident.span, ErrorKind::Eof);
+    assert_eq!(err.render(b"foo"), "[1:1] unexpected EOF\n  |\n1 | foo\n  | ^^^");
+
+    let err = Error::new(Span::unknown(), ErrorKind::Eof);
+    assert_eq!(err.render(src), "unexpected EOF");
+}
+
+#[test]
+fn error_from_buf_iter() {
+    fn parse_eq(src: &[u8]) -> parser::error::Result<()> {
+        let mut iter = buf_iter::BufIter::new(src);
+        iter.next()?;
+        iter.next_as::<b'='>()?;
+        Ok(())
+    }
+
+    let err = parse_eq(b"a:").unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::ExpectFound(b'=', b':')));
+    assert_eq!(err.span, Span::new(1, 1, 1, 2));
+    assert_eq!(err.to_string(), "[1:2] expected `=` found `:`");
+
+    let mut iter = buf_iter::BufIter::new(b"a");
+    iter.next().unwrap();
+    let err = Error::from(iter.next_one_of(b"xy").unwrap_err());
+    assert!(matches!(err.kind, ErrorKind::Other(_)));
+    assert_eq!(err.to_string(), "[1:1] expect one of `x`, `y` found EOF");
+}
+
+#[test]
+fn error_recovery() {
+    let src = b"a = 1; b = ?; c = 3";
+    let mut input = Parser::new(src);
+    let mut values = vec![];
+    let mut errors = vec![];
+
+    while !input.is_empty() {
+        let stmt = input.parse::<Ident>().and_then(|_|{
+            input.parse::<ByteLit<b'='>>()?;
+            input.parse::<LitInt>()
+        });
+        match stmt {
+            Ok(int) => values.push(int.span.evaluate(src)),
+            Err(err) => {
+                errors.push(err);
+                input.skip_until::<b';'>();
+            }
+        }
+        if !input.is_empty() {
+            input.next_as::<b';'>().unwrap();
+        }
+    }
+
+    assert_eq!(values, [b"1", b"3"]);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0].kind, ErrorKind::ExpectDigit(b'?')));
+
+    let mut input = Parser::new(b"abc}def");
+    input.recover_to(b";}");
+    assert_eq!(input.remaining_slice(), b"}def");
+    input.recover_to(b";");
+    assert!(input.is_empty());
+}
@@ -0,0 +1,331 @@
+use parser::{alt, error::ErrorKind, span::Span, token::{ByteLit, GenericToken, Ident, LitChar, LitInt, LitStr, Phrase}, Parse, Parser, Peek, Spanned};
+
+#[test]
+fn parse_map() {
+    struct Name(Span);
+
+    let src = b"  foo_bar baz";
+    let mut input = Parser::new(src);
+
+    let name = input.parse_map(|ident: Ident|Name(ident.span)).unwrap();
+    assert_eq!(name.0.evaluate(src), b"foo_bar");
+}
+
+#[test]
+fn expect_keyword() {
+    let src = b"let x";
+    let mut input = Parser::new(src);
+    let kw = input.expect_keyword(b"let").unwrap();
+    assert_eq!(kw.evaluate(src), b"let");
+    assert_eq!(input.parse::<Ident>().unwrap().span.evaluate(src), b"x");
+
+    let src = b" lot x";
+    let mut input = Parser::new(src);
+    let err = input.expect_keyword(b"let").unwrap_err();
+    assert_eq!(err.to_string(), "[1:2] expected keyword `let`, found `lot`");
+    assert_eq!(input.remaining(), src.len());
+    assert_eq!(input.parse::<Ident>().unwrap().span.evaluate(src), b"lot");
+}
+
+#[test]
+fn parse_str() {
+    let src = b" foo_bar baz";
+    let mut input = Parser::new(src);
+    let (ident, text) = input.parse_str::<Ident>().unwrap();
+    assert_eq!(text, "foo_bar");
+    assert_eq!(ident.span.evaluate(src), b"foo_bar");
+
+    let src = b"\xff\xfe";
+    let mut input = Parser::new(src);
+    let err = input.parse_str::<Phrase>().unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::Utf8(_)));
+}
+
+#[test]
+fn checkpoint_rollback() {
+    let src = b"  fallback rest";
+    let mut input = Parser::new(src);
+
+    let checkpoint = input.checkpoint();
+    assert!(input.parse::<LitStr>().is_err());
+    input.rollback(checkpoint);
+    assert_eq!(input.remaining(), src.len());
+
+    let ident = input.parse::<Ident>().unwrap();
+    assert_eq!(ident.span.evaluate(src), b"fallback");
+    assert_eq!(ident.span.line_col(), (1, 3));
+}
+
+#[test]
+fn consumed_remaining_slice() {
+    let src = b"key value";
+    let mut input = Parser::new(src);
+    assert_eq!(input.consumed_slice(), b"");
+    assert_eq!(input.remaining_slice(), src);
+
+    input.parse::<Ident>().unwrap();
+    assert_eq!(input.consumed_slice(), b"key ");
+    assert_eq!(input.remaining_slice(), b"value");
+}
+
+#[test]
+fn parse_struct() {
+    struct KeyValue {
+        key: Ident,
+        _eq: ByteLit<b'='>,
+        value: Phrase,
+    }
+
+    parser::parse_struct!(KeyValue { key: Ident, _eq: ByteLit<b'='>, value: Phrase });
+
+    let src = b"key=value";
+    let kv = Parser::new(src).parse::<KeyValue>().unwrap();
+    assert_eq!(kv.key.span.evaluate(src), b"key");
+    assert_eq!(kv.value.span.evaluate(src), b"value");
+
+    let src = b"key value";
+    let err = Parser::new(src).parse::<KeyValue>().err().unwrap();
+    assert_eq!(err.to_string(), "[1:5] expected `=` found `v`");
+}
+
+#[test]
+fn parse_enum() {
+    #[derive(Debug)]
+    enum Value {
+        Int(LitInt),
+        Str(LitStr),
+        Ident(Ident),
+    }
+
+    parser::parse_enum!(Value { Int(LitInt), Str(LitStr), Ident(Ident) });
+
+    let src = br#""hi" 42 foo"#;
+    let mut input = Parser::new(src);
+
+    let Value::Str(lit) = input.parse().unwrap() else { panic!("expected Str") };
+    assert_eq!(lit.span.evaluate(src), br#""hi""#);
+
+    let Value::Int(int) = input.parse().unwrap() else { panic!("expected Int") };
+    assert_eq!(int.span.evaluate(src), b"42");
+
+    let Value::Ident(ident) = input.parse().unwrap() else { panic!("expected Ident") };
+    assert_eq!(ident.span.evaluate(src), b"foo");
+
+    let mut input = Parser::new(b"+");
+    let err = input.parse::<Value>().unwrap_err();
+    assert_eq!(err.to_string(), "[1:1] expected alphabetical, found `+`");
+    assert_eq!(input.remaining(), 1);
+}
+
+#[test]
+fn tokens() {
+    let src = br#"a "b"  c;"#;
+    let mut input = Parser::new(src);
+    let tokens = input.tokens().collect::<Result<Vec<_>, _>>().unwrap();
+
+    assert!(matches!(
+        tokens[..],
+        [
+            GenericToken::Ident(_),
+            GenericToken::Whitespace(_),
+            GenericToken::LitStr(_),
+            GenericToken::Whitespace(_),
+            GenericToken::Ident(_),
+            GenericToken::Punct(_),
+        ]
+    ));
+
+    let tokens = tokens.iter().map(|e|e.span().evaluate(src)).collect::<Vec<_>>();
+    assert_eq!(tokens, [&b"a"[..], b" ", br#""b""#, b"  ", b"c", b";"]);
+    assert!(input.is_empty());
+}
+
+#[test]
+fn parse_while() {
+    let src = b" a b  c 1";
+    let mut input = Parser::new(src);
+    let idents = input.parse_while::<Ident>().unwrap();
+    let idents = idents.iter().map(|e|e.span.evaluate(src)).collect::<Vec<_>>();
+    assert_eq!(idents, [b"a", b"b", b"c"]);
+    assert_eq!(input.remaining_slice(), b"1");
+
+    let mut input = Parser::new(b"");
+    assert!(input.parse_while::<Ident>().unwrap().is_empty());
+}
+
+#[test]
+fn parse_opt() {
+    struct Pub(Span);
+
+    impl Peek for Pub {
+        fn peek(input: &Parser) -> parser::error::Result<bool> {
+            let rest = input.remaining_slice();
+            Ok(rest.starts_with(b"pub") && !rest.get(3).is_some_and(|b|b.is_ascii_alphanumeric()))
+        }
+    }
+
+    impl Parse for Pub {
+        fn parse(input: &mut Parser) -> parser::error::Result<Self> {
+            input.expect_keyword(b"pub").map(Pub)
+        }
+    }
+
+    let src = b"pub fn";
+    let mut input = Parser::new(src);
+    let vis = input.parse_opt::<Pub>().unwrap().unwrap();
+    assert_eq!(vis.0.evaluate(src), b"pub");
+    input.expect_keyword(b"fn").unwrap();
+
+    let src = b"public fn";
+    let mut input = Parser::new(src);
+    assert!(input.parse_opt::<Pub>().unwrap().is_none());
+    assert_eq!(input.remaining(), src.len());
+}
+
+#[test]
+fn spanned_evaluate() {
+    fn evaluate<'r, T: Parse + Spanned>(input: &mut Parser, src: &'r [u8]) -> &'r [u8] {
+        input.parse::<T>().unwrap().evaluate(src)
+    }
+
+    let src = b"foo \"bar\"";
+    let mut input = Parser::new(src);
+    assert_eq!(evaluate::<Ident>(&mut input, src), b"foo");
+    assert_eq!(evaluate::<LitStr>(&mut input, src), br#""bar""#);
+}
+
+#[test]
+fn lit_char() {
+    let src = r"'a' '\n' 'é'".as_bytes();
+    let mut input = Parser::new(src);
+    let a = input.parse::<LitChar>().unwrap();
+    assert_eq!(a.span.evaluate(src), b"'a'");
+    assert_eq!(a.value(src).unwrap(), 'a');
+    assert_eq!(input.parse::<LitChar>().unwrap().value(src).unwrap(), '\n');
+    assert_eq!(input.parse::<LitChar>().unwrap().value(src).unwrap(), 'é');
+
+    let err = Parser::new(b"'ab'").parse::<LitChar>().unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::ExpectFound(b'\'', b'b')));
+
+    let err = Parser::new(br"'\q'").parse::<LitChar>().unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::InvalidEscape(b'q')));
+}
+
+#[test]
+fn parse_terminated() {
+    let src = b"a b c} d";
+    let mut input = Parser::new(src);
+    let idents = input.parse_terminated::<Ident, b'}'>().unwrap();
+    let idents = idents.iter().map(|e|e.span.evaluate(src)).collect::<Vec<_>>();
+    assert_eq!(idents, [b"a", b"b", b"c"]);
+    assert_eq!(input.parse::<Ident>().unwrap().span.evaluate(src), b"d");
+
+    let mut input = Parser::new(b" }");
+    assert!(input.parse_terminated::<Ident, b'}'>().unwrap().is_empty());
+    assert!(input.is_empty());
+
+    let err = Parser::new(b"a b").parse_terminated::<Ident, b'}'>().unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::ExpectEof(b'}')));
+}
+
+#[test]
+fn delimited_by() {
+    fn list(input: &mut Parser) -> parser::error::Result<Vec<Ident>> {
+        let mut items = vec![];
+        while input.peek::<Ident>()? {
+            items.push(input.parse::<Ident>()?);
+            if input.parse_opt::<ByteLit<b','>>()?.is_none() {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    let src = b" [ a, b,c, ] d";
+    let mut input = Parser::new(src);
+    let idents = input.delimited_by(b'[', b']', list).unwrap();
+    let idents = idents.iter().map(|e|e.span.evaluate(src)).collect::<Vec<_>>();
+    assert_eq!(idents, [b"a", b"b", b"c"]);
+    assert_eq!(input.parse::<Ident>().unwrap().span.evaluate(src), b"d");
+
+    let mut input = Parser::new(b"[]");
+    assert!(input.delimited_by(b'[', b']', list).unwrap().is_empty());
+
+    let err = Parser::new(b"\n [a, b").delimited_by(b'[', b']', list).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::Unclosed(b'[', b']')));
+    assert_eq!(err.span.offset, 2);
+
+    let err = Parser::new(b"(a)").delimited_by(b'[', b']', list).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::ExpectFound(b'[', b'(')));
+}
+
+#[test]
+fn parse_any() {
+    #[derive(Debug)]
+    enum Value {
+        Str(LitStr),
+        Int(LitInt),
+        Ident(Ident),
+    }
+
+    let src = br#"foo "bar" 42"#;
+    let mut input = Parser::new(src);
+    for _ in 0..3 {
+        match alt!(input, Value::Str, Value::Int, Value::Ident).unwrap() {
+            Value::Ident(ident) => assert_eq!(ident.span.evaluate(src), b"foo"),
+            Value::Str(lit) => assert_eq!(lit.span.evaluate(src), br#""bar""#),
+            Value::Int(int) => assert_eq!(int.span.evaluate(src), b"42"),
+        }
+    }
+    assert!(input.is_empty());
+
+    let mut input = Parser::new(b"a +");
+    input.parse::<Ident>().unwrap();
+    let err = alt!(input, Value::Str, Value::Ident).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::ExpectAlphabetic(b'+')));
+    assert_eq!(input.remaining_slice(), b"+");
+
+    let phrase = input.parse_any(&[|input|input.parse::<Ident>().map(|e|e.span), |input|input.parse::<Phrase>().map(|e|e.span)]);
+    assert_eq!(phrase.unwrap().evaluate(b"a +"), b"+");
+}
+
+#[test]
+fn position_seek() {
+    let src = b"foo\n bar";
+    let mut input = Parser::new(src);
+    assert_eq!(input.offset(), 0);
+    assert_eq!(input.position(), (1, 0));
+
+    input.parse::<Ident>().unwrap();
+    let (line, col) = input.position();
+    let offset = input.offset();
+    assert_eq!(offset, 5);
+    assert_eq!(line, 2);
+
+    let bar = input.parse::<Ident>().unwrap();
+    assert!(input.is_empty());
+
+    input.seek(Span::new(offset, 0, line, col));
+    assert_eq!(input.offset(), offset);
+    assert_eq!(input.parse::<Ident>().unwrap().span, bar.span);
+
+    input.seek(Span::new(0, 0, 1, 0));
+    assert_eq!(input.parse::<Ident>().unwrap().span.evaluate(src), b"foo");
+}
+
+#[test]
+fn token_stream() {
+    use tokenizer::{span::Spanned as _, TokenTree};
+
+    let src = b"let\n  x = 1;";
+    let mut input = Parser::new(src);
+    assert_eq!(input.parse::<Ident>().unwrap().span.evaluate(src), b"let");
+
+    let tokens = input.token_stream().collect::<Vec<_>>();
+    assert!(input.is_empty());
+    assert!(matches!(tokens[0], TokenTree::Ident(_)));
+    assert_eq!(tokens[0].evaluate(src), b"x");
+    assert_eq!(tokens[0].span().line_col(), (2, 3));
+    let tokens = tokens.iter().map(|e|e.evaluate(src)).collect::<Vec<_>>();
+    assert_eq!(tokens, [&b"x"[..], b" ", b"=", b" ", b"1", b";"]);
+}
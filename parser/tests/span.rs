@@ -0,0 +1,40 @@
+use parser::{span::Span, Parser};
+
+#[test]
+fn line_col() {
+    let mut input = Parser::new(b"ab\ncd");
+    for _ in 0..4 {
+        input.next().unwrap();
+    }
+    assert_eq!(input.span().line_col(), (2, 1));
+}
+
+#[test]
+fn try_evaluate() {
+    let span = Span::new(2, 3, 1, 3);
+    assert_eq!(span.range(), 2..5);
+    assert_eq!(span.try_evaluate(b"abcdef"), Some(&b"cde"[..]));
+    assert_eq!(span.try_evaluate(b"abcd"), None);
+    assert_eq!(Span::new(4, 0, 1, 5).try_evaluate(b"abcd"), Some(&b""[..]));
+}
+
+#[test]
+fn is_empty() {
+    let span = Span::new(5, 0, 1, 6);
+    assert!(span.is_empty());
+    assert!(!span.is_unknown());
+    assert_eq!(span.evaluate(b"abcde"), b"");
+    assert_eq!(span.evaluate(b"abcdefg"), b"");
+
+    assert!(Span::unknown().is_empty());
+    assert!(!Span::new(5, 1, 1, 6).is_empty());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde() {
+    let span = Span::new(3, 2, 2, 1);
+    let json = serde_json::to_string(&span).unwrap();
+    assert_eq!(json, r#"{"offset":3,"len":2,"line":2,"col":1}"#);
+    assert_eq!(serde_json::from_str::<Span>(&json).unwrap(), span);
+}
@@ -3,8 +3,10 @@
 //! the root module contains the [`TokenTree`] specification
 //!
 //! the actual tokenizer is contained in [`tokenizer`]
-use span::Span;
-use tokenizer::{Tokenizer, BufIter};
+use std::num::ParseIntError;
+use span::{Span, Spanned};
+use buf_iter::BufIter;
+use tokenizer::Tokenizer;
 
 /// helper to quickly tokenize a source
 ///
@@ -13,37 +15,104 @@ pub fn tokenize(src: &[u8]) -> Vec<TokenTree> {
     Tokenizer::new(src).collect()
 }
 
+/// [`tokenize`] for [`str`] source
+///
+/// use [`Spanned::evaluate_str`] to get the actual value
+pub fn tokenize_str(src: &str) -> Vec<TokenTree> {
+    Tokenizer::from_str(src).collect()
+}
+
 /// a single token
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TokenTree {
     Ident(Ident),
     Punct(Punct),
     Whitespace(Whitespace),
+    /// only emitted when enabled, see [`Tokenizer::with_comments`]
+    Comment(Comment),
+    /// only emitted when enabled, see [`Tokenizer::split_newlines`]
+    Newline(Newline),
+    /// only emitted when enabled, see [`Tokenizer::with_string_literals`] and
+    /// [`Tokenizer::with_number_literals`]
+    Literal(Literal),
+}
+
+impl TokenTree {
+    /// render token with visible escapes, so `\n` and `\t` does not break the output line
+    ///
+    /// see [`u8::escape_ascii`] for the escaping rules
+    pub fn debug_escaped(&self, buf: &[u8]) -> String {
+        self.evaluate(buf).escape_ascii().to_string()
+    }
+
+    /// [`Debug`][std::fmt::Debug] output showing evaluated text and position, see [`DebugWith`]
+    pub fn debug<'r>(&self, buf: &'r [u8]) -> DebugWith<'r> {
+        let name = match self {
+            TokenTree::Ident(_) => "Ident",
+            TokenTree::Punct(_) => "Punct",
+            TokenTree::Whitespace(_) => "Whitespace",
+            TokenTree::Comment(_) => "Comment",
+            TokenTree::Newline(_) => "Newline",
+            TokenTree::Literal(_) => "Literal",
+        };
+        DebugWith::new(name, self.span(), buf)
+    }
+}
+
+/// [`Debug`][std::fmt::Debug] wrapper that show evaluated text of a span
+///
+/// formatted as `Ident("foo" @ 1:3)`, created by [`TokenTree::debug`]
+pub struct DebugWith<'r> {
+    name: &'static str,
+    span: Span,
+    buf: &'r [u8],
+}
+
+impl<'r> DebugWith<'r> {
+    /// create new [`DebugWith`] of a token named `name`
+    pub const fn new(name: &'static str, span: Span, buf: &'r [u8]) -> Self {
+        Self { name, span, buf }
+    }
+}
+
+impl std::fmt::Debug for DebugWith<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = String::from_utf8_lossy(self.span.evaluate(self.buf));
+        let (line,col) = self.span.line_col();
+        write!(f, "{}({text:?} @ {line}:{col})", self.name)
+    }
 }
 
 /// a word consists of alphabetical, numeric, and underscore
 ///
 /// note that identifier may starts with number
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ident {
     span: Span,
 }
 
 impl Ident {
+    /// returns identifier as [`str`]
+    pub fn as_str<'r>(&self, buf: &'r [u8]) -> Result<&'r str, std::str::Utf8Error> {
+        std::str::from_utf8(self.evaluate(buf))
+    }
+
     /// is byte qualified as identifier
     #[inline]
     fn peek(byte: &u8) -> bool {
         matches!(byte,b'A'..=b'Z'|b'a'..=b'z'|b'_'|b'0'..=b'9')
     }
 
-    /// consume iterator resulting identifier
-    fn parse(iter: &mut BufIter<'_>) -> Self {
-        let (mut span, _) = iter.next().expect("should be peeked before");
+    /// consume iterator resulting identifier, continued while `rule` return true
+    fn parse(iter: &mut BufIter<'_>, rule: fn(&u8) -> bool) -> Self {
+        let (mut span, _) = tokenizer::next_byte(iter).expect("should be peeked before");
 
         loop {
             match iter.peek() {
-                Some(byte) if Self::peek(byte) => {
-                    let (end_span, _) = iter.next().unwrap();
+                Some(byte) if rule(byte) => {
+                    let (end_span, _) = tokenizer::next_byte(iter).unwrap();
                     span.spanned_into(end_span);
                 },
                 _ => break
@@ -57,39 +126,84 @@ impl Ident {
 
 /// a punctuation, which anything other than identifier or whitespace
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Punct {
     span: Span,
 }
 
 impl Punct {
-    /// consume iterator resulting punctuation
-    fn parse(iter: &mut BufIter<'_>) -> Self {
-        let (span, _) = iter.next().expect("should be peeked before");
+    /// returns the first byte of punctuation
+    ///
+    /// use [`Punct::as_char`] to match against [`char`], or [`Punct::as_str`] for the whole
+    /// punctuation
+    pub fn as_byte(&self, buf: &[u8]) -> u8 {
+        self.evaluate(buf)[0]
+    }
+
+    /// returns punctuation as [`char`]
+    ///
+    /// lossless for ascii punctuation, for punctuation longer than one byte,
+    /// only the first byte is returned
+    pub fn as_char(&self, buf: &[u8]) -> char {
+        self.as_byte(buf) as char
+    }
+
+    /// returns punctuation as [`str`]
+    pub fn as_str<'r>(&self, buf: &'r [u8]) -> Result<&'r str, std::str::Utf8Error> {
+        std::str::from_utf8(self.evaluate(buf))
+    }
+
+    /// consume iterator resulting punctuation of `n` bytes
+    fn parse_n(iter: &mut BufIter<'_>, n: usize) -> Self {
+        let (mut span, _) = tokenizer::next_byte(iter).expect("should be peeked before");
+        for _ in 1..n {
+            let (end_span, _) = tokenizer::next_byte(iter).expect("operator should be matched before");
+            span.spanned_into(end_span);
+        }
         Self { span }
     }
 }
 
-/// a whitespace, which specified in [`u8::is_ascii_whitespace`]
+/// a whitespace, which by default specified in [`u8::is_ascii_whitespace`]
+///
+/// see [`Tokenizer::with_whitespace`]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Whitespace {
     span: Span,
 }
 
 impl Whitespace {
+    /// count `\n` in current whitespace
+    pub fn newline_count(&self, buf: &[u8]) -> usize {
+        self.evaluate(buf).iter().filter(|b|**b == b'\n').count()
+    }
+
+    /// is current whitespace contains `\n`
+    pub fn contains_newline(&self, buf: &[u8]) -> bool {
+        self.evaluate(buf).contains(&b'\n')
+    }
+
     /// is byte qualified as whitespace, see [`u8::is_ascii_whitespace`]
     #[inline]
     fn peek(byte: &u8) -> bool {
         byte.is_ascii_whitespace()
     }
 
-    /// consume iterator resulting whitespaces
-    fn parse(iter: &mut BufIter<'_>) -> Self {
-        let (mut span, _) = iter.next().expect("should be peeked before");
+    /// consume iterator resulting whitespace of `n` bytes
+    fn parse_n(iter: &mut BufIter<'_>, n: usize) -> Self {
+        let Punct { span } = Punct::parse_n(iter, n);
+        Self { span }
+    }
+
+    /// consume iterator resulting whitespaces, continued while `rule` return true
+    fn parse(iter: &mut BufIter<'_>, rule: fn(&u8) -> bool) -> Self {
+        let (mut span, _) = tokenizer::next_byte(iter).expect("should be peeked before");
 
         loop {
             match iter.peek() {
-                Some(byte) if Self::peek(byte) => {
-                    let (end_span, _) = iter.next().unwrap();
+                Some(byte) if rule(byte) => {
+                    let (end_span, _) = tokenizer::next_byte(iter).unwrap();
                     span.spanned_into(end_span);
                 },
                 _ => break
@@ -100,108 +214,882 @@ impl Whitespace {
     }
 }
 
+/// a run of `\n` or `\r\n`
+///
+/// see [`Tokenizer::split_newlines`]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Newline {
+    span: Span,
+}
+
+impl Newline {
+    /// count line breaks in current newline run
+    pub fn count(&self, buf: &[u8]) -> usize {
+        self.evaluate(buf).iter().filter(|b|**b == b'\n').count()
+    }
+
+    /// length of newline run at the start of given bytes
+    fn peek_len(rest: &[u8]) -> usize {
+        let mut len = 0;
+        loop {
+            match &rest[len..] {
+                [b'\n', ..] => len += 1,
+                [b'\r', b'\n', ..] => len += 2,
+                _ => break len,
+            }
+        }
+    }
+
+    /// consume iterator resulting newline run of `n` bytes
+    fn parse_n(iter: &mut BufIter<'_>, n: usize) -> Self {
+        let Punct { span } = Punct::parse_n(iter, n);
+        Self { span }
+    }
+}
+
+/// a string literal, `"a\"b"`, or raw string literal, `r#"a"b"#`, including its delimiter,
+/// or a numeric literal, `1_000` or `0xFF`, including its prefix
+///
+/// see [`Tokenizer::with_string_literals`] and [`Tokenizer::with_number_literals`]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Literal {
+    span: Span,
+    /// count of `#` of raw string, [`None`] for non raw string
+    hashes: Option<usize>,
+    /// radix of numeric literal, [`None`] for string literal
+    radix: Option<u32>,
+    terminated: bool,
+}
+
+impl Literal {
+    /// radix of numeric literal, 16, 8, 2 or 10, [`None`] for string literal
+    pub const fn radix(&self) -> Option<u32> {
+        self.radix
+    }
+
+    /// parse numeric literal value, ignoring `_` separators
+    ///
+    /// returns error for string literal, literal without digits like `0x`, or overflow
+    pub fn value_u64(&self, buf: &[u8]) -> Result<u64, ParseIntError> {
+        let digits = match self.radix {
+            Some(_) => self.content(buf).iter().filter(|b|**b != b'_').map(|b|*b as char).collect(),
+            None => String::new(),
+        };
+        u64::from_str_radix(&digits, self.radix.unwrap_or(10))
+    }
+
+    /// is raw string literal, where `\` is not an escape
+    pub const fn is_raw(&self) -> bool {
+        self.hashes.is_some()
+    }
+
+    /// is literal closed before EOF
+    ///
+    /// tokenizer is infallible, so unclosed literal is emitted up to EOF
+    pub const fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+
+    /// literal content without delimiters or numeric prefix, escapes are not processed
+    pub fn content<'r>(&self, buf: &'r [u8]) -> &'r [u8] {
+        let lit = self.evaluate(buf);
+        let (open, close) = match (self.hashes, self.radix) {
+            (_, Some(10)) => (0, 0),
+            (_, Some(_)) => (2, 0),
+            (Some(hashes), None) => (hashes + 2, hashes + 1),
+            (None, None) => (1, 1),
+        };
+        let close = if self.terminated { close } else { 0 };
+        lit.get(open..lit.len() - close).unwrap_or_default()
+    }
+
+    /// length of literal at the start of given bytes, its raw hashes and whether its terminated
+    fn peek_len(rest: &[u8]) -> Option<(usize, Option<usize>, bool)> {
+        match rest {
+            [b'"', ..] => {
+                let mut i = 1;
+                while i < rest.len() {
+                    match rest[i] {
+                        b'\\' => i += 2,
+                        b'"' => return Some((i + 1, None, true)),
+                        _ => i += 1,
+                    }
+                }
+                Some((rest.len(), None, false))
+            }
+            [b'r', tail @ ..] => {
+                let hashes = tail.iter().take_while(|b|**b == b'#').count();
+                if tail.get(hashes) != Some(&b'"') {
+                    return None;
+                }
+                let start = hashes + 2;
+                let closed = (start..rest.len()).find(|i|{
+                    rest[*i] == b'"' && rest.get(i + 1..i + 1 + hashes).is_some_and(|e|e.iter().all(|b|*b == b'#'))
+                });
+                match closed {
+                    Some(i) => Some((i + 1 + hashes, Some(hashes), true)),
+                    None => Some((rest.len(), Some(hashes), false)),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// length of numeric literal at the start of given bytes and its radix
+    ///
+    /// prefix without digits, like `0x` in `0xG`, is still a literal which fails to parse
+    fn peek_number_len(rest: &[u8]) -> Option<(usize, u32)> {
+        if !rest.first()?.is_ascii_digit() {
+            return None;
+        }
+        let (prefix, radix) = match rest {
+            [b'0', b'x', ..] => (2, 16),
+            [b'0', b'o', ..] => (2, 8),
+            [b'0', b'b', ..] => (2, 2),
+            _ => (0, 10),
+        };
+        let digits = rest[prefix..]
+            .iter()
+            .take_while(|b|**b == b'_' || (**b as char).is_digit(radix))
+            .count();
+        Some((prefix + digits, radix))
+    }
+
+    /// consume iterator resulting literal of `n` bytes
+    fn parse_n(iter: &mut BufIter<'_>, n: usize, hashes: Option<usize>, terminated: bool) -> Self {
+        let Punct { span } = Punct::parse_n(iter, n);
+        Self { span, hashes, radix: None, terminated }
+    }
+
+    /// consume iterator resulting numeric literal of `n` bytes
+    fn parse_number_n(iter: &mut BufIter<'_>, n: usize, radix: u32) -> Self {
+        let Punct { span } = Punct::parse_n(iter, n);
+        Self { span, hashes: None, radix: Some(radix), terminated: true }
+    }
+}
+
+/// a line or block comment, including its delimiter
+///
+/// see [`Tokenizer::with_comments`]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Comment {
+    span: Span,
+    terminated: bool,
+}
+
+impl Comment {
+    /// is block comment closed before EOF
+    ///
+    /// tokenizer is infallible, so unclosed block comment is emitted up to EOF, always true
+    /// for line comment
+    pub const fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+
+    /// consume iterator resulting comment of `n` bytes
+    fn parse_n(iter: &mut BufIter<'_>, n: usize, terminated: bool) -> Self {
+        let Punct { span } = Punct::parse_n(iter, n);
+        Self { span, terminated }
+    }
+}
+
+/// a single byte, see [`tokenizer::ByteTokenizer`]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ByteToken {
+    span: Span,
+    byte: u8,
+}
+
+impl ByteToken {
+    /// returns the byte
+    pub const fn byte(&self) -> u8 {
+        self.byte
+    }
+}
 
 pub mod tokenizer {
     //! the actual tokenizer
-    use std::{iter, slice};
+    use std::{collections::VecDeque, str::Utf8Error};
+    use buf_iter::BufIter;
     use crate::span::Spanned;
 
-    use super::{TokenTree, Ident, Punct, Whitespace};
+    use super::{TokenTree, ByteToken, Comment, Ident, Literal, Newline, Punct, Whitespace};
     use super::span::Span;
 
-    type SlicePeek<'r> = iter::Peekable<slice::Iter<'r,u8>>;
-
     /// iterator that yield [`TokenTree`]
-    #[derive(Debug)]
+    ///
+    /// use [`Tokenizer::iter`] or iterate `&Tokenizer` to scan the same source again
+    #[derive(Debug, Clone)]
     pub struct Tokenizer<'r> {
-        iter: BufIter<'r>
+        iter: BufIter<'r>,
+        /// starting position, see [`Tokenizer::iter`]
+        origin: BufIter<'r>,
+        operators: Vec<&'r [u8]>,
+        line_comment: &'r [u8],
+        block_comment: (&'r [u8], &'r [u8]),
+        ident_start: fn(&u8) -> bool,
+        ident_continue: fn(&u8) -> bool,
+        whitespace: fn(&u8) -> bool,
+        split_newlines: bool,
+        string_literals: bool,
+        number_literals: bool,
+        /// span of the last consumed byte, see [`Spanned::span`]
+        last: Span,
     }
 
     impl<'r> Tokenizer<'r> {
         /// create new tokenizer from a source
         pub fn new(buf: &'r [u8]) -> Self {
-            Self { iter: BufIter::new(buf) }
+            Self::with_iter(BufIter::new(buf))
+        }
+
+        /// create new tokenizer starting from given span
+        ///
+        /// this can be used to tokenize part of larger source while keeping correct line and
+        /// column
+        pub fn from_span(buf: &'r [u8], span: Span) -> Self {
+            Self::with_iter(iter_from_span(buf, span))
+        }
+
+        fn with_iter(iter: BufIter<'r>) -> Self {
+            Self {
+                last: first_span(&iter),
+                origin: iter.clone(),
+                iter,
+                operators: vec![],
+                line_comment: b"",
+                block_comment: (b"", b""),
+                ident_start: Ident::peek,
+                ident_continue: Ident::peek,
+                whitespace: Whitespace::peek,
+                split_newlines: false,
+                string_literals: false,
+                number_literals: false,
+            }
+        }
+
+        /// create new tokenizer from a [`str`] source
+        #[allow(clippy::should_implement_trait)]
+        pub fn from_str(src: &'r str) -> Self {
+            Self::new(src.as_bytes())
+        }
+
+        /// create new tokenizer from a source that must be valid utf8
+        ///
+        /// the whole source is validated up front, so token evaluated with
+        /// [`Spanned::evaluate`] can be converted to [`str`] without failing, as long as the
+        /// token does not split a multi byte character, see [`Spanned::evaluate_str`]
+        pub fn new_checked(buf: &'r [u8]) -> Result<Self, Utf8Error> {
+            std::str::from_utf8(buf)?;
+            Ok(Self::new(buf))
+        }
+
+        /// group multi byte operators into single [`Punct`]
+        ///
+        /// longest match is used, so with `["<<", "<<=", "<"]`, `<<=` is one punctuation
+        ///
+        /// ```
+        /// # use tokenizer::{tokenizer::Tokenizer, span::Spanned};
+        /// let src = b"a <<= b";
+        /// let tokens = Tokenizer::new(src).with_operators(&["<<", "<<=", "<"]).skip_whitespace();
+        /// let tokens = tokens.map(|e|e.evaluate(src)).collect::<Vec<_>>();
+        /// assert_eq!(tokens, [&b"a"[..], b"<<=", b"b"]);
+        /// ```
+        pub fn with_operators(mut self, operators: &[&'r str]) -> Self {
+            self.operators = operators.iter().map(|e|e.as_bytes()).collect();
+            self.operators.sort_by_key(|e|std::cmp::Reverse(e.len()));
+            self
+        }
+
+        /// emit comments as [`TokenTree::Comment`] instead of tokenizing its content
+        ///
+        /// line comment continue until `\n`, which is not included, block comment continue
+        /// until the closing delimiter, or EOF, see [`Comment::is_terminated`]
+        ///
+        /// empty delimiter disable its comment kind
+        ///
+        /// ```
+        /// # use tokenizer::{tokenizer::Tokenizer, span::Spanned};
+        /// let src = b"a /* x */ b // y";
+        /// let tokens = Tokenizer::new(src).with_comments(b"//", (b"/*", b"*/")).skip_whitespace();
+        /// let tokens = tokens.map(|e|e.evaluate(src)).collect::<Vec<_>>();
+        /// assert_eq!(tokens, [&b"a"[..], b"/* x */", b"b", b"// y"]);
+        /// ```
+        pub fn with_comments(mut self, line: &'r [u8], block: (&'r [u8], &'r [u8])) -> Self {
+            self.line_comment = line;
+            self.block_comment = block;
+            self
+        }
+
+        /// define which byte starts and continues an [`Ident`]
+        ///
+        /// by default both are ascii alphanumeric or `_`, so identifier may starts with number,
+        /// byte that does not start an identifier or whitespace is a [`Punct`]
+        ///
+        /// ```
+        /// # use tokenizer::{tokenizer::Tokenizer, span::Spanned};
+        /// fn start(b: &u8) -> bool { b.is_ascii_alphabetic() || matches!(b, b'_' | b'-') }
+        /// fn cont(b: &u8) -> bool { b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-') }
+        ///
+        /// let src = b"-webkit-box";
+        /// let tokens = Tokenizer::new(src).with_ident_rule(start, cont);
+        /// let tokens = tokens.map(|e|e.evaluate(src)).collect::<Vec<_>>();
+        /// assert_eq!(tokens, [b"-webkit-box"]);
+        /// ```
+        pub fn with_ident_rule(mut self, start: fn(&u8) -> bool, continue_: fn(&u8) -> bool) -> Self {
+            self.ident_start = start;
+            self.ident_continue = continue_;
+            self
+        }
+
+        /// set which byte is a [`Whitespace`], default to [`u8::is_ascii_whitespace`]
+        ///
+        /// ```
+        /// # use tokenizer::{tokenizer::Tokenizer, span::Spanned};
+        /// fn space(b: &u8) -> bool { matches!(b, b' ' | b'\t' | b'\n' | b'\r') }
+        ///
+        /// let src = b"a\x0C b";
+        /// let tokens = Tokenizer::new(src).with_whitespace(space).skip_whitespace();
+        /// let tokens = tokens.map(|e|e.evaluate(src)).collect::<Vec<_>>();
+        /// assert_eq!(tokens, [b"a", b"\x0C", b"b"]);
+        /// ```
+        pub fn with_whitespace(mut self, whitespace: fn(&u8) -> bool) -> Self {
+            self.whitespace = whitespace;
+            self
+        }
+
+        /// emit line breaks as [`TokenTree::Newline`] separated from other whitespace
+        ///
+        /// consecutive `\n` or `\r\n` is one [`Newline`], so `a \n b` is `a`, ` `, `\n`, ` `
+        /// and `b`, note that [`SkipWhitespace`] does not skip [`TokenTree::Newline`]
+        pub fn split_newlines(mut self) -> Self {
+            self.split_newlines = true;
+            self
+        }
+
+        /// emit string literals as [`TokenTree::Literal`] instead of tokenizing its content
+        ///
+        /// `"..."` literal may contains `\` escaped quote, while raw `r"..."` or `r#"..."#`
+        /// literal ends at the first `"` followed by the same count of `#`, see [`Literal`]
+        ///
+        /// ```
+        /// # use tokenizer::{tokenizer::Tokenizer, span::Spanned};
+        /// let src = br##"a r#"b"c"# "d\"""##;
+        /// let tokens = Tokenizer::new(src).with_string_literals().skip_whitespace();
+        /// let tokens = tokens.map(|e|e.evaluate(src)).collect::<Vec<_>>();
+        /// assert_eq!(tokens, [&b"a"[..], br##"r#"b"c"#"##, br#""d\"""#]);
+        /// ```
+        pub fn with_string_literals(mut self) -> Self {
+            self.string_literals = true;
+            self
+        }
+
+        /// emit numeric literals as [`TokenTree::Literal`] instead of identifier
+        ///
+        /// literal starts with ascii digit, may have `0x`, `0o` or `0b` prefix, and may
+        /// contains `_` separator, see [`Literal::radix`]
+        ///
+        /// ```
+        /// # use tokenizer::{tokenizer::Tokenizer, span::Spanned};
+        /// let src = b"0xFFpx 1_000";
+        /// let tokens = Tokenizer::new(src).with_number_literals().skip_whitespace();
+        /// let tokens = tokens.map(|e|e.evaluate(src)).collect::<Vec<_>>();
+        /// assert_eq!(tokens, [&b"0xFF"[..], b"px", b"1_000"]);
+        /// ```
+        pub fn with_number_literals(mut self) -> Self {
+            self.number_literals = true;
+            self
+        }
+
+        /// create fresh tokenizer from the starting position, keeping its configuration
+        ///
+        /// the returned tokenizer only borrow the source, so the same source can be scanned
+        /// multiple times
+        pub fn iter(&self) -> Tokenizer<'r> {
+            Tokenizer { iter: self.origin.clone(), last: first_span(&self.origin), ..self.clone() }
+        }
+
+        /// move tokenizer to given span, keeping its configuration
+        ///
+        /// next token starts at the span, see [`Tokenizer::from_span`], can be used to
+        /// re-tokenize an edited region
+        pub fn reset_to(&mut self, span: Span) {
+            self.iter = iter_from_span(self.iter.source(), span);
+            self.last = first_span(&self.iter);
+        }
+
+        /// length of comment at current position and whether its terminated
+        fn peek_comment(&self) -> Option<(usize, bool)> {
+            let rest = self.iter.remaining_slice();
+            let line = self.line_comment;
+            if !line.is_empty() && rest.starts_with(line) {
+                let len = rest.iter().position(|b|*b == b'\n').unwrap_or(rest.len());
+                return Some((len, true));
+            }
+            let (open, close) = self.block_comment;
+            if !open.is_empty() && rest.starts_with(open) {
+                let body = &rest[open.len()..];
+                return match body.windows(close.len().max(1)).position(|e|e == close) {
+                    Some(pos) => Some((open.len() + pos + close.len(), true)),
+                    None => Some((rest.len(), false)),
+                };
+            }
+            None
         }
 
         pub fn peekable_tokens<const N: usize>(self) -> Peekable<'r,N> {
             Peekable::new(self)
         }
-    }
 
-    impl<'r> Iterator for Tokenizer<'r> {
-        type Item = TokenTree;
+        /// create iterator that skip [`TokenTree::Whitespace`]
+        pub fn skip_whitespace(self) -> SkipWhitespace<'r> {
+            SkipWhitespace { iter: self }
+        }
 
-        fn next(&mut self) -> Option<Self::Item> {
+        /// create iterator that attach trailing whitespace, newline and comment to the preceding
+        /// token, see [`TokenWithTrivia`]
+        pub fn with_trivia(self) -> WithTrivia<'r> {
+            WithTrivia { iter: self, peeked: None }
+        }
+
+        /// split tokens at the first token matching given predicate
+        ///
+        /// returns the tokens before the matched token, and tokenizer that resume after it
+        ///
+        /// the matched token itself is consumed, if none matched, the returned tokenizer is empty
+        pub fn split_at<F>(mut self, pred: F) -> (Vec<TokenTree>, Tokenizer<'r>) where F: Fn(&TokenTree) -> bool {
+            let mut before = vec![];
+            for tree in self.by_ref() {
+                if pred(&tree) {
+                    break;
+                }
+                before.push(tree);
+            }
+            (before, self)
+        }
+
+        /// consume the next token
+        fn next_tree(&mut self) -> Option<TokenTree> {
             // tokenizer should not advanced iterator
             // instead the tokens should
+            if let Some((len, terminated)) = self.peek_comment() {
+                return Some(TokenTree::Comment(Comment::parse_n(&mut self.iter, len, terminated)));
+            }
+
+            if self.string_literals {
+                if let Some((len, hashes, terminated)) = Literal::peek_len(self.iter.remaining_slice()) {
+                    return Some(TokenTree::Literal(Literal::parse_n(&mut self.iter, len, hashes, terminated)));
+                }
+            }
+
+            if self.number_literals {
+                if let Some((len, radix)) = Literal::peek_number_len(self.iter.remaining_slice()) {
+                    return Some(TokenTree::Literal(Literal::parse_number_n(&mut self.iter, len, radix)));
+                }
+            }
+
             let tree = match self.iter.peek()? {
-                byte if byte.is_ascii_whitespace() => TokenTree::Whitespace(Whitespace::parse(&mut self.iter)),
-                byte if Ident::peek(byte) => TokenTree::Ident(Ident::parse(&mut self.iter)),
-                _ => TokenTree::Punct(Punct::parse(&mut self.iter)),
+                byte if (self.whitespace)(byte) && self.split_newlines => {
+                    let rest = self.iter.remaining_slice();
+                    match Newline::peek_len(rest) {
+                        0 => {
+                            let len = (0..rest.len())
+                                .take_while(|i|(self.whitespace)(&rest[*i]) && Newline::peek_len(&rest[*i..]) == 0)
+                                .count();
+                            TokenTree::Whitespace(Whitespace::parse_n(&mut self.iter, len))
+                        }
+                        len => TokenTree::Newline(Newline::parse_n(&mut self.iter, len)),
+                    }
+                }
+                byte if (self.whitespace)(byte) => TokenTree::Whitespace(Whitespace::parse(&mut self.iter, self.whitespace)),
+                byte if (self.ident_start)(byte) => TokenTree::Ident(Ident::parse(&mut self.iter, self.ident_continue)),
+                _ => {
+                    let rest = self.iter.remaining_slice();
+                    let len = self.operators
+                        .iter()
+                        .find(|op|!op.is_empty() && rest.starts_with(op))
+                        .map_or(1, |op|op.len());
+                    TokenTree::Punct(Punct::parse_n(&mut self.iter, len))
+                }
             };
 
             Some(tree)
         }
     }
 
+    impl<'r> Iterator for Tokenizer<'r> {
+        type Item = TokenTree;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let tree = self.next_tree()?;
+            self.last = last_byte(self.iter.source(), tree.span());
+            Some(tree)
+        }
+    }
+
+    impl<'r> IntoIterator for &Tokenizer<'r> {
+        type Item = TokenTree;
+        type IntoIter = Tokenizer<'r>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter()
+        }
+    }
+
     impl Spanned for Tokenizer<'_> {
+        fn span(&self) -> Span {
+            self.last.clone()
+        }
+    }
+
+    /// significant token with the trivia that follows it
+    ///
+    /// trivia is [`TokenTree::Whitespace`], [`TokenTree::Newline`] or [`TokenTree::Comment`],
+    /// trivia at the start of source have no preceding token, so its first tree is yielded as
+    /// the token
+    #[derive(Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct TokenWithTrivia {
+        pub token: TokenTree,
+        /// span of all trivia until the next significant token
+        pub trailing_ws: Option<Span>,
+    }
+
+    /// iterator that yield [`TokenWithTrivia`]
+    ///
+    /// created by [`Tokenizer::with_trivia`]
+    #[derive(Debug)]
+    pub struct WithTrivia<'r> {
+        iter: Tokenizer<'r>,
+        peeked: Option<TokenTree>,
+    }
+
+    impl Iterator for WithTrivia<'_> {
+        type Item = TokenWithTrivia;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let token = match self.peeked.take() {
+                Some(token) => token,
+                None => self.iter.next()?,
+            };
+
+            let mut trailing_ws: Option<Span> = None;
+            for tree in self.iter.by_ref() {
+                if !matches!(tree, TokenTree::Whitespace(_) | TokenTree::Newline(_) | TokenTree::Comment(_)) {
+                    self.peeked = Some(tree);
+                    break;
+                }
+                match &mut trailing_ws {
+                    Some(span) => span.spanned_into(tree.span()),
+                    None => trailing_ws = Some(tree.span()),
+                }
+            }
+
+            Some(TokenWithTrivia { token, trailing_ws })
+        }
+    }
+
+    /// iterator that yield [`TokenTree`] other than [`TokenTree::Whitespace`]
+    ///
+    /// created by [`Tokenizer::skip_whitespace`]
+    #[derive(Debug)]
+    pub struct SkipWhitespace<'r> {
+        iter: Tokenizer<'r>
+    }
+
+    impl<'r> Iterator for SkipWhitespace<'r> {
+        type Item = TokenTree;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            loop {
+                match self.iter.next()? {
+                    TokenTree::Whitespace(_) => continue,
+                    tree => return Some(tree),
+                }
+            }
+        }
+    }
+
+    impl Spanned for SkipWhitespace<'_> {
         fn span(&self) -> Span {
             self.iter.span()
         }
     }
 
-    /// iterator that track [`Span`] and yield a byte from source buffer
+    /// token yielded by [`IndentTokenizer`]
     #[derive(Debug)]
-    pub struct BufIter<'b> {
-        iter: SlicePeek<'b>,
-        last_span: Span,
-        offset: usize,
-        line: usize,
-        col: usize,
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum IndentToken {
+        /// token other than newline and leading whitespace
+        Tree(TokenTree),
+        Newline(Newline),
+        /// line indented deeper than the current level, with span of the leading whitespace
+        Indent(Span),
+        /// line indented less than the previous line, one for each closed level, with zero
+        /// length span at the line first token
+        Dedent(Span),
+    }
+
+    /// tokenizer that track indentation of each line, like python lexer
+    ///
+    /// leading whitespace of a line is replaced with [`IndentToken::Indent`] or
+    /// [`IndentToken::Dedent`] when the indentation changes, blank lines does not change
+    /// indentation, and all open levels are closed at EOF
+    ///
+    /// dedent to a width between two open levels closes the deeper levels and opens a new level
+    /// at that width, yielding [`IndentToken::Dedent`] followed by [`IndentToken::Indent`]
+    ///
+    /// ```
+    /// # use tokenizer::tokenizer::{IndentTokenizer, IndentToken};
+    /// let src = b"a\n  b\nc";
+    /// let tokens = IndentTokenizer::new(src).filter(|e|!matches!(e, IndentToken::Tree(_)));
+    /// assert_eq!(tokens.count(), 4);
+    /// ```
+    #[derive(Debug)]
+    pub struct IndentTokenizer<'r> {
+        buf: &'r [u8],
+        iter: Tokenizer<'r>,
+        tab_width: usize,
+        /// indentation width of each open level, the first is always 0
+        levels: Vec<usize>,
+        pending: VecDeque<IndentToken>,
+        line_start: bool,
     }
 
-    impl<'b> BufIter<'b> {
-        /// create new [`BufIter`] from source buffer
-        pub fn new(buf: &'b [u8]) -> Self {
+    impl<'r> IndentTokenizer<'r> {
+        /// create new indentation tracking tokenizer from a source
+        pub fn new(buf: &'r [u8]) -> Self {
             Self {
-                iter: buf.iter().peekable(),
-                last_span: Span::new(0, 1, 1, 1),
-                offset: 0, line: 1, col: 1,
+                buf,
+                iter: Tokenizer::new(buf).split_newlines(),
+                tab_width: 1,
+                levels: vec![0],
+                pending: VecDeque::new(),
+                line_start: true,
             }
         }
 
-        /// peek the next byte, see [`std::iter::Peekable::peek`]
-        pub fn peek(&mut self) -> Option<&&u8> {
-            self.iter.peek()
+        /// set `\t` to advance indentation to the next tab stop of given width
+        ///
+        /// by default, `\t` advance indentation by one like a space
+        pub fn with_tab_width(mut self, width: usize) -> Self {
+            self.tab_width = width;
+            self
+        }
+
+        /// indentation width of given leading whitespace
+        fn width(&self, span: &Span) -> usize {
+            span.evaluate(self.buf).iter().fold(0, |col, b| match b {
+                b'\t' if self.tab_width > 1 => (col / self.tab_width + 1) * self.tab_width,
+                _ => col + 1,
+            })
+        }
+
+        /// queue [`IndentToken::Dedent`] for each level deeper than `width`
+        fn dedent(&mut self, width: usize, span: Span) {
+            while self.levels.len() > 1 && self.levels[self.levels.len() - 1] > width {
+                self.levels.pop();
+                self.pending.push_back(IndentToken::Dedent(span.clone()));
+            }
         }
     }
 
-    impl<'r> Iterator for BufIter<'r> {
-        type Item = (Span, &'r u8);
+    impl Iterator for IndentTokenizer<'_> {
+        type Item = IndentToken;
 
         fn next(&mut self) -> Option<Self::Item> {
-            let byte = self.iter.next()?;
-            self.last_span = Span::new(self.offset, 1, self.line, self.col);
+            loop {
+                if let Some(token) = self.pending.pop_front() {
+                    return Some(token);
+                }
+
+                let Some(tree) = self.iter.next() else {
+                    let (line, col) = self.iter.span().line_col();
+                    self.dedent(0, Span::point(self.buf.len(), line, col));
+                    return self.pending.pop_front();
+                };
+
+                match tree {
+                    TokenTree::Newline(newline) => {
+                        self.line_start = true;
+                        return Some(IndentToken::Newline(newline));
+                    }
+                    TokenTree::Whitespace(ws) if self.line_start => {
+                        // blank line does not change indentation
+                        if matches!(self.iter.clone().next(), None | Some(TokenTree::Newline(_))) {
+                            continue;
+                        }
+                        self.line_start = false;
+                        let width = self.width(&ws.span());
+                        if width > self.levels[self.levels.len() - 1] {
+                            self.levels.push(width);
+                            return Some(IndentToken::Indent(ws.span()));
+                        }
+                        let (line, col) = self.iter.span().line_col();
+                        self.dedent(width, Span::point(ws.span().offset() + ws.span().len(), line, col + 1));
+                        if width > self.levels[self.levels.len() - 1] {
+                            self.levels.push(width);
+                            self.pending.push_back(IndentToken::Indent(ws.span()));
+                        }
+                    }
+                    tree if self.line_start => {
+                        self.line_start = false;
+                        let span = tree.span();
+                        let (line, col) = span.line_col();
+                        self.dedent(0, Span::point(span.offset(), line, col));
+                        self.pending.push_back(IndentToken::Tree(tree));
+                    }
+                    tree => return Some(IndentToken::Tree(tree)),
+                }
+            }
+        }
+    }
+
+    /// tokenizer that accept chunked input
+    ///
+    /// the last token of a chunk may continue in the next chunk, so its kept until the next
+    /// [`StreamTokenizer::feed`] or [`StreamTokenizer::finish`]
+    ///
+    /// spans are relative to the whole stream, only the kept token is buffered
+    #[derive(Debug)]
+    pub struct StreamTokenizer {
+        pending: Vec<u8>,
+        offset: usize,
+        line: usize,
+        col: usize,
+    }
+
+    impl StreamTokenizer {
+        /// create new stream tokenizer
+        pub const fn new() -> Self {
+            Self { pending: Vec::new(), offset: 0, line: 1, col: 1 }
+        }
 
-            self.offset += 1;
+        /// tokenize next chunk, returning tokens that are complete
+        pub fn feed(&mut self, chunk: &[u8]) -> Vec<TokenTree> {
+            self.pending.extend_from_slice(chunk);
+
+            let mut tokens = Tokenizer::new(&self.pending).collect::<Vec<_>>();
+            let consumed = match tokens.last() {
+                Some(TokenTree::Punct(_)) | None => self.pending.len(),
+                Some(tree) => {
+                    let offset = tree.span().offset();
+                    tokens.pop();
+                    offset
+                }
+            };
+
+            let tokens = tokens.into_iter().map(|tree|self.relocate(tree)).collect();
+
+            for byte in self.pending.drain(..consumed) {
+                if byte == b'\n' {
+                    self.line += 1;
+                    self.col = 1;
+                } else {
+                    self.col += 1;
+                }
+            }
+            self.offset += consumed;
+
+            tokens
+        }
 
-            if byte == &b'\n' {
-                self.line += 1;
-                self.col = 1;
-            } else {
-                self.col += 1;
+        /// end of stream, returning the kept token if any
+        pub fn finish(self) -> Option<TokenTree> {
+            let tree = Tokenizer::new(&self.pending).next()?;
+            Some(self.relocate(tree))
+        }
+
+        /// move span of token from pending buffer into the stream
+        fn relocate(&self, tree: TokenTree) -> TokenTree {
+            let relocate = |span: Span| span.shift(self.offset).shift_line(self.line - 1, self.col - 1);
+            match tree {
+                TokenTree::Ident(ident) => TokenTree::Ident(Ident { span: relocate(ident.span) }),
+                TokenTree::Punct(punct) => TokenTree::Punct(Punct { span: relocate(punct.span) }),
+                TokenTree::Whitespace(ws) => TokenTree::Whitespace(Whitespace { span: relocate(ws.span) }),
+                TokenTree::Comment(comment) => TokenTree::Comment(Comment { span: relocate(comment.span), ..comment }),
+                TokenTree::Newline(newline) => TokenTree::Newline(Newline { span: relocate(newline.span) }),
+                TokenTree::Literal(lit) => TokenTree::Literal(Literal { span: relocate(lit.span), ..lit }),
             }
+        }
+    }
+
+    impl Default for StreamTokenizer {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// iterator that yield every byte as [`ByteToken`]
+    ///
+    /// the lowest level tokenizer, when [`TokenTree`] categorization is not wanted
+    #[derive(Debug)]
+    pub struct ByteTokenizer<'r> {
+        iter: BufIter<'r>,
+        /// span of the last consumed byte, see [`Spanned::span`]
+        last: Span,
+    }
+
+    impl<'r> ByteTokenizer<'r> {
+        /// create new byte tokenizer from a source
+        pub fn new(buf: &'r [u8]) -> Self {
+            let iter = BufIter::new(buf);
+            Self { last: first_span(&iter), iter }
+        }
+    }
 
-            Some((self.last_span.clone(), byte))
+    impl<'r> Iterator for ByteTokenizer<'r> {
+        type Item = ByteToken;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let (span, byte) = next_byte(&mut self.iter)?;
+            self.last = span.clone();
+            Some(ByteToken { span, byte })
         }
     }
 
-    impl Spanned for BufIter<'_> {
+    impl Spanned for ByteTokenizer<'_> {
         fn span(&self) -> Span {
-            self.last_span.clone()
+            self.last.clone()
         }
     }
 
+    /// consume the next byte along with its span
+    pub(crate) fn next_byte(iter: &mut BufIter<'_>) -> Option<(Span, u8)> {
+        let (span, byte) = iter.next_spanned()?;
+        let (line, col) = span.line_col();
+        Some((Span::new(span.offset, span.len, line, col), byte))
+    }
+
+    /// span of the next byte, used as the last span before any byte consumed
+    fn first_span(iter: &BufIter<'_>) -> Span {
+        next_byte(&mut iter.clone()).map_or(Span::new(0, 1, 1, 1), |(span, _)|span)
+    }
+
+    /// span of the last byte of given token span
+    fn last_byte(buf: &[u8], span: Span) -> Span {
+        let (line, col) = span.line_col();
+        let body = &span.evaluate(buf)[..span.len() - 1];
+        let (line, col) = match body.iter().rposition(|b|*b == b'\n') {
+            Some(last) => (line + body.iter().filter(|b|**b == b'\n').count(), body.len() - last),
+            None => (line, col + body.len()),
+        };
+        Span::new(span.offset() + body.len(), 1, line, col)
+    }
+
+    /// [`BufIter`] starting at given span
+    ///
+    /// [`BufIter::from_span`] expects the column already consumed on the line before the span,
+    /// which is one less than the 1-based tokenizer column of the span first byte
+    fn iter_from_span(buf: &[u8], span: Span) -> BufIter<'_> {
+        let (line, col) = span.line_col();
+        BufIter::from_span(buf, buf_iter::Span::new(span.offset(), 1, line, col.saturating_sub(1)))
+    }
+
     #[derive(Debug)]
     pub struct Peekable<'r,const N: usize = 3> {
         iter: Tokenizer<'r>,
@@ -216,7 +1104,7 @@ pub mod tokenizer {
 
         /// peek n forward
         ///
-        /// this is 0 indexed, so `peen_n(0)` will peek once
+        /// this is 0 indexed, so `peek_n(0)` is the next token, same as [`Peekable::peek`]
         ///
         /// panic if `n >= N`
         pub fn peek_n(&mut self, n: usize) -> Option<&TokenTree> {
@@ -242,6 +1130,27 @@ pub mod tokenizer {
         pub fn peek3(&mut self) -> Option<&TokenTree> {
             self.peek_n(2)
         }
+
+        /// consume consecutive [`Punct`] without whitespace between them as one operator
+        ///
+        /// so `==` and `&&` is a single span, while `= =` is two, return [`None`] without
+        /// consuming if next token is not [`Punct`]
+        ///
+        /// this is the dynamic alternative of [`Tokenizer::with_operators`]
+        pub fn next_operator(&mut self) -> Option<Span> {
+            let Some(TokenTree::Punct(_)) = self.peek() else {
+                return None;
+            };
+            let mut span = self.next().expect("peeked").span();
+            while let Some(TokenTree::Punct(punct)) = self.peek() {
+                if punct.span.offset() != span.offset() + span.len() {
+                    break;
+                }
+                let punct = self.next().expect("peeked");
+                span.spanned_into(punct.span());
+            }
+            Some(span)
+        }
     }
 
     impl<'r,const N: usize> Iterator for Peekable<'r,N> {
@@ -278,6 +1187,24 @@ pub mod tokenizer {
     mod test {
         use super::*;
 
+        #[test]
+        #[cfg(feature = "serde")]
+        fn test_serde() {
+            let span = Span::new(3, 2, 2, 1);
+            let json = serde_json::to_string(&span).unwrap();
+            assert_eq!(json, r#"{"offset":3,"len":2,"line":2,"col":1}"#);
+            assert_eq!(serde_json::from_str::<Span>(&json).unwrap(), span);
+
+            let src = b"a +";
+            let tokens = Tokenizer::new(src).collect::<Vec<_>>();
+            let json = serde_json::to_string(&tokens).unwrap();
+            let de = serde_json::from_str::<Vec<TokenTree>>(&json).unwrap();
+            assert_eq!(de.len(), tokens.len());
+            for (de, tree) in de.iter().zip(&tokens) {
+                assert_eq!(de.span(), tree.span());
+            }
+        }
+
         #[test]
         fn test_peekable_span() {
             let src = b"<!DOCTYPE html> <html>";
@@ -289,16 +1216,488 @@ pub mod tokenizer {
 
             assert_eq!(tk.span().offset(), span.offset());
         }
+
+        #[test]
+        fn test_next_operator() {
+            let src = b"a == b === c = =d";
+            let mut tk = Tokenizer::new(src).peekable_tokens::<1>();
+            let mut ops = vec![];
+            while tk.peek().is_some() {
+                match tk.next_operator() {
+                    Some(op) => ops.push(op.evaluate(src)),
+                    None => { tk.next(); },
+                }
+            }
+            assert_eq!(ops, [&b"=="[..], b"===", b"=", b"="]);
+
+            let mut tk = Tokenizer::new(b"a").peekable_tokens::<1>();
+            assert!(tk.next_operator().is_none());
+            assert!(tk.next().is_some());
+        }
+
+        #[test]
+        fn test_punct_as_char() {
+            let src = b"a+b";
+            let tokens = Tokenizer::new(src).collect::<Vec<_>>();
+            let TokenTree::Punct(punct) = &tokens[1] else {
+                panic!("expected punct")
+            };
+            assert_eq!(punct.as_char(src), '+');
+            assert_eq!(punct.as_str(src), Ok("+"));
+        }
+
+        #[test]
+        fn test_punct_as_byte() {
+            let src = b"<div>";
+            let tokens = Tokenizer::new(src).collect::<Vec<_>>();
+            let TokenTree::Punct(punct) = &tokens[0] else {
+                panic!("expected punct")
+            };
+            assert_eq!(punct.as_byte(src), b'<');
+        }
+
+        #[test]
+        fn test_ident_as_str() {
+            let src = b"foo_bar1 baz";
+            let tokens = Tokenizer::new(src).collect::<Vec<_>>();
+            let TokenTree::Ident(ident) = &tokens[0] else {
+                panic!("expected ident")
+            };
+            assert_eq!(ident.as_str(src), Ok("foo_bar1"));
+        }
+
+        #[test]
+        fn test_tokenize_str() {
+            let spans = |tokens: Vec<TokenTree>| tokens.iter().map(|e|e.span()).collect::<Vec<_>>();
+            assert_eq!(spans(crate::tokenize_str("a b")), spans(crate::tokenize(b"a b")));
+
+            let src = "key = value";
+            let tokens = Tokenizer::from_str(src).map(|e|e.evaluate_str(src)).collect::<Vec<_>>();
+            assert_eq!(tokens, ["key", " ", "=", " ", "value"]);
+        }
+
+        #[test]
+        fn test_skip_whitespace() {
+            let src = b"a  b   c";
+            let tokens = Tokenizer::new(src).skip_whitespace().collect::<Vec<_>>();
+            assert_eq!(tokens.len(), 3);
+            assert!(tokens.iter().all(|e|matches!(e,TokenTree::Ident(_))));
+
+            let tokens = tokens.iter().map(|e|e.evaluate(src)).collect::<Vec<_>>();
+            assert_eq!(tokens, [b"a", b"b", b"c"]);
+        }
+
+        #[test]
+        fn test_byte_tokenizer() {
+            let src = b"a b\nc";
+            let tokens = ByteTokenizer::new(src).collect::<Vec<_>>();
+            assert_eq!(tokens.len(), src.len());
+
+            for (i, token) in tokens.iter().enumerate() {
+                assert_eq!(token.span().offset(), i);
+                assert_eq!(token.byte(), src[i]);
+                assert_eq!(token.evaluate(src), &src[i..i + 1]);
+            }
+            assert_eq!(tokens[4].span().line_col(), (2, 1));
+        }
+
+        #[test]
+        fn test_byte_tokenizer_position() {
+            let src = b"a\nb";
+            let mut tokens = ByteTokenizer::new(src);
+            assert_eq!(tokens.span(), Span::new(0, 1, 1, 1));
+
+            tokens.next().unwrap();
+            tokens.next().unwrap();
+            assert_eq!(tokens.span(), Span::new(1, 1, 1, 2));
+            tokens.next().unwrap();
+            assert_eq!(tokens.span(), Span::new(2, 1, 2, 1));
+            assert!(tokens.next().is_none());
+            assert_eq!(tokens.span(), Span::new(2, 1, 2, 1));
+        }
+
+        #[test]
+        fn test_tokenizer_position() {
+            let src = b"a\n\t bc\n";
+            let mut tokens = Tokenizer::new(src);
+            assert_eq!(tokens.span().line_col(), (1, 1));
+
+            let spans = [(b"a" as &[u8], (1, 1), (1, 1)), (b"\n\t ", (1, 2), (2, 2)), (b"bc", (2, 3), (2, 4)), (b"\n", (2, 5), (2, 5))];
+            for (text, start, last) in spans {
+                let tree = tokens.next().unwrap();
+                assert_eq!(tree.evaluate(src), text);
+                assert_eq!(tree.span().line_col(), start);
+                assert_eq!(tokens.span().line_col(), last);
+            }
+            assert!(tokens.next().is_none());
+            assert_eq!(tokens.span().offset(), src.len() - 1);
+        }
+
+        #[test]
+        fn test_indent_tokenizer() {
+            let src = b"if a\n    if b\n\t  c\n  \n    d\ne\n  f";
+            let tokens = IndentTokenizer::new(src).with_tab_width(4).map(|e|match e {
+                IndentToken::Tree(tree) => format!("{:?}", std::str::from_utf8(tree.evaluate(src)).unwrap()),
+                IndentToken::Newline(_) => "NL".into(),
+                IndentToken::Indent(_) => "IN".into(),
+                IndentToken::Dedent(_) => "DE".into(),
+            }).collect::<Vec<_>>();
+
+            assert_eq!(tokens, [
+                "\"if\"", "\" \"", "\"a\"", "NL",
+                "IN", "\"if\"", "\" \"", "\"b\"", "NL",
+                "IN", "\"c\"", "NL", "NL",
+                "DE", "\"d\"", "NL",
+                "DE", "\"e\"", "NL",
+                "IN", "\"f\"", "DE",
+            ]);
+        }
+
+        #[test]
+        fn test_indent_tokenizer_uneven_dedent() {
+            let src = b"a\n    b\n  c\n  d\n";
+            let tokens = IndentTokenizer::new(src).map(|e|match e {
+                IndentToken::Tree(tree) => format!("{:?}", std::str::from_utf8(tree.evaluate(src)).unwrap()),
+                IndentToken::Newline(_) => "NL".into(),
+                IndentToken::Indent(_) => "IN".into(),
+                IndentToken::Dedent(_) => "DE".into(),
+            }).collect::<Vec<_>>();
+
+            assert_eq!(tokens, [
+                "\"a\"", "NL",
+                "IN", "\"b\"", "NL",
+                "DE", "IN", "\"c\"", "NL",
+                "\"d\"", "NL",
+                "DE",
+            ]);
+        }
+
+        #[test]
+        fn test_new_checked() {
+            let src = "a = \"caf\u{e9}\"".as_bytes();
+            let tokens = Tokenizer::new_checked(src).unwrap().with_string_literals().collect::<Vec<_>>();
+            let TokenTree::Literal(lit) = &tokens[4] else {
+                panic!("expected literal")
+            };
+            assert_eq!(std::str::from_utf8(lit.content(src)), Ok("caf\u{e9}"));
+
+            let err = Tokenizer::new_checked(b"ok \xff\xfe").unwrap_err();
+            assert_eq!(err.valid_up_to(), 3);
+        }
+
+        #[test]
+        fn test_with_whitespace() {
+            fn space(b: &u8) -> bool {
+                matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+            }
+
+            let src = b"a \x0C\tb\xA0c";
+            let tokens = Tokenizer::new(src).collect::<Vec<_>>();
+            assert_eq!(tokens[1].evaluate(src), b" \x0C\t");
+
+            let tokens = Tokenizer::new(src).with_whitespace(space).collect::<Vec<_>>();
+            let tokens = tokens.iter().map(|e|e.evaluate(src)).collect::<Vec<_>>();
+            assert_eq!(tokens, [&b"a"[..], b" ", b"\x0C", b"\t", b"b", b"\xA0", b"c"]);
+
+            let nbsp = |b: &u8| space(b) || *b == 0xA0;
+            let tokens = Tokenizer::new(src).with_whitespace(nbsp).skip_whitespace().collect::<Vec<_>>();
+            let tokens = tokens.iter().map(|e|e.evaluate(src)).collect::<Vec<_>>();
+            assert_eq!(tokens, [&b"a"[..], b"\x0C", b"b", b"c"]);
+        }
+
+        #[test]
+        fn test_from_span() {
+            let src = b"<html>\n  <body>\n";
+            let mut tokens = Tokenizer::from_span(src, Span::new(9, 1, 2, 3));
+
+            let tree = tokens.next().unwrap();
+            assert_eq!(tree.evaluate(src), b"<");
+            assert_eq!(tree.span().line_col(), (2, 3));
+
+            let tree = tokens.next().unwrap();
+            assert_eq!(tree.evaluate(src), b"body");
+            assert_eq!(tree.span().line_col(), (2, 4));
+        }
+
+        #[test]
+        fn test_raw_string_literal() {
+            let src = br##"r"a\b" r#"has "quotes""# raw "x\"y" r#"open"##;
+            let tokens = Tokenizer::new(src).with_string_literals().skip_whitespace().collect::<Vec<_>>();
+            assert_eq!(tokens.len(), 5);
+
+            let lits = tokens.iter().filter_map(|e|match e {
+                TokenTree::Literal(lit) => Some(lit),
+                _ => None,
+            }).collect::<Vec<_>>();
+
+            assert!(lits[0].is_raw());
+            assert_eq!(lits[0].content(src), br"a\b");
+            assert!(lits[1].is_raw());
+            assert_eq!(lits[1].evaluate(src), br##"r#"has "quotes""#"##);
+            assert_eq!(lits[1].content(src), br#"has "quotes""#);
+            assert!(!lits[2].is_raw());
+            assert_eq!(lits[2].content(src), br#"x\"y"#);
+            assert!(!lits[3].is_terminated());
+            assert_eq!(lits[3].content(src), b"open");
+
+            assert_eq!(tokens[2].evaluate(src), b"raw");
+        }
+
+        #[test]
+        fn test_number_literal() {
+            let src = b"0xFF 0b1010 1_000 0o17 0x 0xG";
+            let tokens = Tokenizer::new(src).with_number_literals().skip_whitespace().collect::<Vec<_>>();
+            assert_eq!(tokens.len(), 7);
+
+            let lits = tokens.iter().filter_map(|e|match e {
+                TokenTree::Literal(lit) => Some(lit),
+                _ => None,
+            }).collect::<Vec<_>>();
+
+            assert_eq!(lits[0].radix(), Some(16));
+            assert_eq!(lits[0].value_u64(src), Ok(255));
+            assert_eq!(lits[1].radix(), Some(2));
+            assert_eq!(lits[1].value_u64(src), Ok(10));
+            assert_eq!(lits[2].radix(), Some(10));
+            assert_eq!(lits[2].value_u64(src), Ok(1000));
+            assert_eq!(lits[3].value_u64(src), Ok(15));
+            assert_eq!(lits[4].evaluate(src), b"0x");
+            assert!(lits[4].value_u64(src).is_err());
+            assert_eq!(lits[5].evaluate(src), b"0x");
+            assert!(lits[5].value_u64(src).is_err());
+
+            assert_eq!(tokens[6].evaluate(src), b"G");
+        }
+
+        #[test]
+        fn test_split_newlines() {
+            let src = b"a \n b";
+            let tokens = Tokenizer::new(src).split_newlines().skip_whitespace().collect::<Vec<_>>();
+            assert_eq!(tokens.len(), 3);
+            let TokenTree::Newline(newline) = &tokens[1] else {
+                panic!("expected newline")
+            };
+            assert_eq!(newline.evaluate(src), b"\n");
+
+            let src = b"a\t\r\n\n  b";
+            let tokens = Tokenizer::new(src).split_newlines().collect::<Vec<_>>();
+            let TokenTree::Newline(newline) = &tokens[2] else {
+                panic!("expected newline")
+            };
+            assert_eq!(newline.count(src), 2);
+            let tokens = tokens.iter().map(|e|e.evaluate(src)).collect::<Vec<_>>();
+            assert_eq!(tokens, [&b"a"[..], b"\t", b"\r\n\n", b"  ", b"b"]);
+        }
+
+        #[test]
+        fn test_ident_rule() {
+            fn css_start(b: &u8) -> bool { b.is_ascii_alphabetic() || matches!(b, b'_' | b'-') }
+            fn css_continue(b: &u8) -> bool { b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-') }
+
+            let src = b"a-b -c2";
+            let tokens = Tokenizer::new(src).with_ident_rule(css_start, css_continue).collect::<Vec<_>>();
+            assert!(matches!(tokens[2], TokenTree::Ident(_)));
+            let tokens = tokens.iter().map(|e|e.evaluate(src)).collect::<Vec<_>>();
+            assert_eq!(tokens, [&b"a-b"[..], b" ", b"-c2"]);
+
+            fn start(b: &u8) -> bool { b.is_ascii_alphabetic() || b == &b'_' }
+            fn cont(b: &u8) -> bool { b.is_ascii_alphanumeric() || b == &b'_' }
+
+            let src = b"1ab a1";
+            let tokens = Tokenizer::new(src).with_ident_rule(start, cont).collect::<Vec<_>>();
+            assert!(matches!(tokens[0], TokenTree::Punct(_)));
+            let tokens = tokens.iter().map(|e|e.evaluate(src)).collect::<Vec<_>>();
+            assert_eq!(tokens, [&b"1"[..], b"ab", b" ", b"a1"]);
+
+            let default = Tokenizer::new(src).collect::<Vec<_>>();
+            assert_eq!(default[0].evaluate(src), b"1ab");
+        }
+
+        #[test]
+        fn test_iter_twice() {
+            let src = b"a <= b";
+            let mut tokens = Tokenizer::new(src).with_operators(&["<="]);
+            tokens.next();
+
+            let first = (&tokens).into_iter().count();
+            let second = tokens.iter().count();
+            assert_eq!(first, 5);
+            assert_eq!(first, second);
+
+            let mut count = 0;
+            for tree in &tokens {
+                assert_eq!(tree.span(), tokens.iter().nth(count).unwrap().span());
+                count += 1;
+            }
+            assert_eq!(count, 5);
+            assert_eq!(tokens.count(), 4);
+        }
+
+        #[test]
+        fn test_reset_to() {
+            let src = b"a <= b\n  c";
+            let mut tokens = Tokenizer::new(src).with_operators(&["<="]);
+            let first = tokens.by_ref().map(|e|e.span()).collect::<Vec<_>>();
+            assert_eq!(first.len(), 7);
+
+            tokens.reset_to(first[2].clone());
+            let again = tokens.map(|e|e.span()).collect::<Vec<_>>();
+            assert_eq!(again, first[2..]);
+        }
+
+        #[test]
+        fn test_with_operators() {
+            let src = b"a <<= b << c<d";
+            let tokens = Tokenizer::new(src)
+                .with_operators(&["<<", "<<=", "<"])
+                .skip_whitespace()
+                .collect::<Vec<_>>();
+            assert!(matches!(tokens[1], TokenTree::Punct(_)));
+
+            let tokens = tokens.iter().map(|e|e.evaluate(src)).collect::<Vec<_>>();
+            assert_eq!(tokens, [&b"a"[..], b"<<=", b"b", b"<<", b"c", b"<", b"d"]);
+        }
+
+        #[test]
+        fn test_whitespace_newline() {
+            let src = b"a  \n\n  b c";
+            let tokens = Tokenizer::new(src).collect::<Vec<_>>();
+            let (TokenTree::Whitespace(paragraph), TokenTree::Whitespace(space)) = (&tokens[1], &tokens[3]) else {
+                panic!("expected whitespace")
+            };
+            assert_eq!(paragraph.newline_count(src), 2);
+            assert!(paragraph.contains_newline(src));
+            assert_eq!(space.newline_count(src), 0);
+            assert!(!space.contains_newline(src));
+        }
+
+        #[test]
+        fn test_debug_with() {
+            let src = b"a\n  foo";
+            let tokens = Tokenizer::new(src).collect::<Vec<_>>();
+            assert_eq!(format!("{:?}", tokens[2].debug(src)), r#"Ident("foo" @ 2:3)"#);
+            assert_eq!(format!("{:?}", tokens[1].debug(src)), r#"Whitespace("\n  " @ 1:2)"#);
+        }
+
+        #[test]
+        fn test_debug_escaped() {
+            let src = b"a\n\t b";
+            let tokens = Tokenizer::new(src).collect::<Vec<_>>();
+            assert_eq!(tokens[0].debug_escaped(src), "a");
+            assert_eq!(tokens[1].debug_escaped(src), "\\n\\t ");
+            assert!(!tokens[1].debug_escaped(src).contains('\n'));
+        }
+
+        #[test]
+        fn test_stream_tokenizer() {
+            let src = b"ab\ncd ef";
+            let mut stream = StreamTokenizer::new();
+
+            assert!(stream.feed(&src[..2]).is_empty());
+
+            let tokens = stream.feed(&src[2..4]);
+            assert_eq!(tokens.len(), 2);
+            assert_eq!(tokens[0].evaluate(src), b"ab");
+            assert_eq!(tokens[1].evaluate(src), b"\n");
+
+            let tokens = stream.feed(&src[4..]);
+            assert_eq!(tokens.len(), 2);
+            assert_eq!(tokens[0].evaluate(src), b"cd");
+            assert_eq!(tokens[0].span().line_col(), (2, 1));
+            assert_eq!(tokens[1].evaluate(src), b" ");
+
+            let last = stream.finish().unwrap();
+            assert_eq!(last.evaluate(src), b"ef");
+            assert_eq!(last.span().line_col(), (2, 4));
+        }
+
+        #[test]
+        fn test_stream_tokenizer_joined() {
+            let src = b"abcd ef";
+            let mut stream = StreamTokenizer::new();
+
+            assert!(stream.feed(b"ab").is_empty());
+            let tokens = stream.feed(b"cd ef");
+            assert!(matches!(tokens[0], TokenTree::Ident(_)));
+            assert_eq!(tokens[0].evaluate(src), b"abcd");
+            assert_eq!(tokens[1].evaluate(src), b" ");
+            assert_eq!(stream.finish().unwrap().evaluate(src), b"ef");
+        }
+
+        #[test]
+        fn test_line_comment() {
+            let src = b"a // x\nb";
+            let tokens = Tokenizer::new(src).with_comments(b"//", (b"/*", b"*/")).collect::<Vec<_>>();
+            let TokenTree::Comment(comment) = &tokens[2] else {
+                panic!("expected comment")
+            };
+            assert_eq!(comment.evaluate(src), b"// x");
+            assert!(comment.is_terminated());
+
+            let tokens = tokens.iter().map(|e|e.evaluate(src)).collect::<Vec<_>>();
+            assert_eq!(tokens, [&b"a"[..], b" ", b"// x", b"\n", b"b"]);
+        }
+
+        #[test]
+        fn test_block_comment() {
+            let src = b"a /* x\n*/ b /* y";
+            let tokens = Tokenizer::new(src).with_comments(b"//", (b"/*", b"*/")).collect::<Vec<_>>();
+            let (TokenTree::Comment(closed), TokenTree::Comment(open)) = (&tokens[2], &tokens[6]) else {
+                panic!("expected comment")
+            };
+            assert_eq!(closed.evaluate(src), b"/* x\n*/");
+            assert!(closed.is_terminated());
+            assert_eq!(open.evaluate(src), b"/* y");
+            assert!(!open.is_terminated());
+
+            assert_eq!(tokens[4].evaluate(src), b"b");
+            assert_eq!(tokens[4].span().line_col(), (2, 4));
+        }
+
+        #[test]
+        fn test_with_trivia() {
+            let src = b"a  b";
+            let tokens = Tokenizer::new(src).with_trivia().collect::<Vec<_>>();
+            assert_eq!(tokens.len(), 2);
+            assert_eq!(tokens[0].token.evaluate(src), b"a");
+            assert_eq!(tokens[0].trailing_ws.as_ref().unwrap().evaluate(src), b"  ");
+            assert_eq!(tokens[1].token.evaluate(src), b"b");
+            assert!(tokens[1].trailing_ws.is_none());
+
+            let src = b" x // note\n\t;";
+            let tokens = Tokenizer::new(src).with_comments(b"//", (b"", b"")).with_trivia().collect::<Vec<_>>();
+            let tokens = tokens.iter().map(|e|(e.token.evaluate(src), e.trailing_ws.as_ref().map(|e|e.evaluate(src)))).collect::<Vec<_>>();
+            assert_eq!(tokens, [
+                (&b" "[..], None),
+                (b"x", Some(&b" // note\n\t"[..])),
+                (b";", None),
+            ]);
+        }
+
+        #[test]
+        fn test_split_at() {
+            let src = b"key = value";
+            let (before, rest) = Tokenizer::new(src).split_at(|tree| {
+                matches!(tree,TokenTree::Punct(punct) if punct.evaluate(src) == b"=")
+            });
+
+            let before = before.iter().map(|e|e.evaluate(src)).collect::<Vec<_>>();
+            assert_eq!(before, [&b"key"[..], b" "]);
+
+            let rest = rest.map(|e|e.evaluate(src)).collect::<Vec<_>>();
+            assert_eq!(rest, [&b" "[..], b"value"]);
+        }
     }
 
 }
 
 pub mod span {
     //! see [`Span`]
-    use super::{TokenTree, Ident, Punct, Whitespace};
+    use super::{TokenTree, ByteToken, Comment, Ident, Literal, Newline, Punct, Whitespace};
 
     /// map of a character to actual buffer
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Span {
         offset: usize,
         len: usize,
@@ -312,9 +1711,21 @@ pub mod span {
             Self { offset, len, line, col }
         }
 
-        /// set length to provided span
+        /// create zero length span, pointing at a position between bytes
+        ///
+        /// evaluate to an empty slice
+        pub const fn point(offset: usize, line: usize, col: usize) -> Self {
+            Self { offset, len: 0, line, col }
+        }
+
+        /// returns span with given length, start position is kept
+        pub const fn with_len(&self, len: usize) -> Span {
+            Span { len, ..*self }
+        }
+
+        /// set length to cover until the end of provided span
         pub fn spanned_into(&mut self, span: Span) {
-            self.len = span.offset - self.offset + 1;
+            self.len = span.offset + span.len - self.offset;
         }
 
         /// returns (line, column) of the source
@@ -323,11 +1734,18 @@ pub mod span {
         }
 
         /// get span len
-        #[allow(clippy::len_without_is_empty)]
         pub const fn len(&self) -> usize {
             self.len
         }
 
+        /// is span zero length, see [`Span::point`]
+        ///
+        /// unlike [`Self::is_unknown`], empty span may point at a real offset, and evaluate to
+        /// an empty slice
+        pub const fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
         /// get span offset
         pub const fn offset(&self) -> usize {
             self.offset
@@ -347,6 +1765,94 @@ pub mod span {
         pub const fn unknown() -> Self {
             Self { offset: 0, len: 0, line: 0, col: 0 }
         }
+
+        /// returns span with offset moved forward by `delta`
+        ///
+        /// use this to relocate span from tokenizing a sub slice into the parent buffer, where
+        /// `delta` is the sub slice start, line and col is kept, see [`Span::shift_line`]
+        pub const fn shift(&self, delta: usize) -> Span {
+            Span { offset: self.offset + delta, ..*self }
+        }
+
+        /// returns span with line moved forward by `lines`
+        ///
+        /// `cols` is only added when span is on the first line, because only the first line of
+        /// the sub slice does not start at column 1 in the parent buffer
+        pub const fn shift_line(&self, lines: usize, cols: usize) -> Span {
+            let col = if self.line == 1 { self.col + cols } else { self.col };
+            Span { line: self.line + lines, col, ..*self }
+        }
+
+        /// check is given offset within current span
+        ///
+        /// start is inclusive and end is exclusive, zero length or unknown span contains nothing
+        pub const fn contains(&self, offset: usize) -> bool {
+            self.offset <= offset && offset < self.offset + self.len
+        }
+
+        /// check is current span share any offset with given span
+        ///
+        /// touching spans does not overlap, zero length or unknown span overlaps nothing
+        pub const fn overlaps(&self, other: &Span) -> bool {
+            self.len != 0 && other.len != 0 &&
+            self.offset < other.offset + other.len &&
+            other.offset < self.offset + self.len
+        }
+
+        /// [`Display`][std::fmt::Display] start and end position, computed from given buffer
+        ///
+        /// formatted as `line:col-line:col`, where the end is the position of the last byte
+        pub fn display_with<'r>(&self, buf: &'r [u8]) -> DisplayWith<'r> {
+            DisplayWith { span: self.clone(), buf }
+        }
+    }
+
+    /// formatted as `offset..offset+len (line:col)`
+    impl std::fmt::Display for Span {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}..{} ({}:{})", self.offset, self.offset + self.len, self.line, self.col)
+        }
+    }
+
+    /// span display with end position, created by [`Span::display_with`]
+    #[derive(Debug)]
+    pub struct DisplayWith<'r> {
+        span: Span,
+        buf: &'r [u8],
+    }
+
+    impl std::fmt::Display for DisplayWith<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let Span { offset, len, line, col } = self.span;
+            let head = self.buf.get(offset..offset + len.saturating_sub(1)).unwrap_or_default();
+            let (end_line, end_col) = head.iter().fold((line, col), |(line, col), b| match b {
+                b'\n' => (line + 1, 1),
+                _ => (line, col + 1),
+            });
+            write!(f, "{line}:{col}-{end_line}:{end_col}")
+        }
+    }
+
+    /// map span of transformed buffer back to the original buffer
+    ///
+    /// `table` is `(out, in)` offset pairs sorted by `out`, where output offset `out` starts
+    /// at input offset `in`, the nearest pair at or before the span offset is used, span
+    /// before the first pair is returned as is, length, line and col is kept
+    ///
+    /// ```
+    /// # use tokenizer::span::{remap, Span};
+    /// // "a  b" preprocessed into "a b"
+    /// let span = remap(&Span::new(2, 1, 1, 3), &[(0, 0), (2, 3)]);
+    /// assert_eq!(span.offset(), 3);
+    /// ```
+    pub fn remap(span: &Span, table: &[(usize, usize)]) -> Span {
+        match table.partition_point(|(out, _)|*out <= span.offset) {
+            0 => span.clone(),
+            i => {
+                let (out, in_) = table[i - 1];
+                Span { offset: in_ + (span.offset - out), ..span.clone() }
+            }
+        }
     }
 
     /// a trait helper to work with [`Span`]
@@ -358,6 +1864,22 @@ pub mod span {
             let span = self.span();
             &buf[span.offset..span.offset + span.len]
         }
+        /// [`Spanned::evaluate`] for [`str`] source
+        ///
+        /// # Panic
+        ///
+        /// panic if span is not on char boundary, which can happen for [`Punct`] of non ascii
+        /// character, because non ascii character is tokenized per byte
+        fn evaluate_str<'r>(&self, src: &'r str) -> &'r str {
+            let span = self.span();
+            &src[span.offset..span.offset + span.len]
+        }
+    }
+
+    impl Spanned for Span {
+        fn span(&self) -> Span {
+            self.clone()
+        }
     }
 
     impl Spanned for Ident {
@@ -378,16 +1900,143 @@ pub mod span {
         }
     }
 
+    impl Spanned for Literal {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
+    impl Spanned for Newline {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
+    impl Spanned for Comment {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
+    impl Spanned for ByteToken {
+        fn span(&self) -> Span {
+            self.span.clone()
+        }
+    }
+
     impl Spanned for TokenTree {
         fn span(&self) -> Span {
             match self {
                 TokenTree::Ident(ident) => ident.span(),
                 TokenTree::Punct(punct) => punct.span(),
                 TokenTree::Whitespace(whitespace) => whitespace.span(),
+                TokenTree::Comment(comment) => comment.span(),
+                TokenTree::Newline(newline) => newline.span(),
+                TokenTree::Literal(lit) => lit.span(),
             }
         }
     }
 
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_contains() {
+            let span = Span::new(2, 3, 1, 3);
+            assert!(!span.contains(1));
+            assert!(span.contains(2));
+            assert!(span.contains(4));
+            assert!(!span.contains(5));
+
+            assert!(!Span::new(2, 0, 1, 3).contains(2));
+            assert!(!Span::unknown().contains(0));
+        }
+
+        #[test]
+        fn test_spanned_into() {
+            let mut span = Span::new(2, 1, 1, 3);
+            span.spanned_into(Span::new(5, 1, 1, 6));
+            assert_eq!(span.len(), 4);
+
+            span.spanned_into(Span::new(7, 5, 1, 8));
+            assert_eq!(span.len(), 10);
+        }
+
+        #[test]
+        fn test_point() {
+            let src = b"ab";
+            let point = Span::point(1, 1, 2);
+            assert_eq!(point.len(), 0);
+            assert!(point.is_empty());
+            assert_eq!(point.evaluate(src), b"");
+            assert!(!point.is_unknown());
+
+            let span = point.with_len(1);
+            assert_eq!(span.evaluate(src), b"b");
+            assert_eq!(span.line_col(), (1, 2));
+        }
+
+        #[test]
+        fn test_shift() {
+            let src = b"<p>\n  <b>bold</b>";
+            let start = 9;
+            let sub = &src[start..];
+
+            let tokens = crate::tokenize(sub);
+            let bold = tokens[0].span().shift(start).shift_line(1, 5);
+            assert_eq!(bold.evaluate(src), b"bold");
+            assert_eq!(bold.line_col(), (2, 6));
+
+            let span = Span::new(4, 1, 2, 3).shift(10).shift_line(1, 5);
+            assert_eq!(span.offset(), 14);
+            assert_eq!(span.line_col(), (3, 3));
+        }
+
+        #[test]
+        fn test_remap() {
+            // `{x}` expanded into `value` in the output
+            let input = b"a {x} b";
+            let output = b"a value b";
+            let table = [(0, 0), (2, 2), (7, 5)];
+
+            let b = remap(&Span::new(8, 1, 1, 9), &table);
+            assert_eq!(b.evaluate(input), b"b");
+            assert_eq!(b.evaluate(input), Span::new(8, 1, 1, 9).evaluate(output));
+
+            let value = remap(&Span::new(2, 5, 1, 3), &table);
+            assert_eq!(value.offset(), 2);
+            assert_eq!(value.len(), 5);
+
+            assert_eq!(remap(&Span::new(1, 1, 1, 2), &[(2, 4)]).offset(), 1);
+            assert_eq!(remap(&Span::new(1, 1, 1, 2), &[]).offset(), 1);
+        }
+
+        #[test]
+        fn test_display() {
+            let src = b"ab\ncd";
+            let span = Span::new(1, 3, 1, 2);
+            assert_eq!(span.to_string(), "1..4 (1:2)");
+            assert_eq!(span.display_with(src).to_string(), "1:2-2:1");
+
+            let tokens = crate::tokenize(b"a\n  bcd");
+            assert_eq!(tokens[2].span().display_with(b"a\n  bcd").to_string(), "2:3-2:5");
+        }
+
+        #[test]
+        fn test_overlaps() {
+            let span = Span::new(2, 3, 1, 3);
+            assert!(span.overlaps(&Span::new(4, 2, 1, 5)));
+            assert!(span.overlaps(&Span::new(0, 3, 1, 1)));
+            assert!(span.overlaps(&Span::new(3, 1, 1, 4)));
+            assert!(!span.overlaps(&Span::new(5, 2, 1, 6)));
+            assert!(!span.overlaps(&Span::new(0, 2, 1, 1)));
+
+            assert!(!span.overlaps(&Span::new(3, 0, 1, 4)));
+            assert!(!span.overlaps(&Span::unknown()));
+        }
+    }
+
 }
 
 